@@ -0,0 +1,308 @@
+/**
+ * TRUST GAME HOST: LOCAL PROVER / VERIFIER
+ *
+ * Proves the trust-game guest program locally with the SP1 SDK, without the
+ * Charms CLI. The host:
+ * 1. Loads the guest ELF and a JSON `ProveInput`
+ * 2. Computes the expected `ProveOutput` natively
+ * 3. Proves the guest with SP1's CPU prover (or the mock prover)
+ * 4. Verifies the proof and checks its public values against the expected output
+ *
+ * Before proving, `execute` runs the guest under the SP1 executor only and
+ * reports cycle counts per phase (see `CYCLE_PHASES`), and `bench` does the
+ * same for synthetic 10/100/1000/10000-move histories as a markdown table.
+ *
+ * Incremental proofs: `--previous <proof>` loads an earlier compressed proof
+ * of the same guest, passes it to the guest for recursive verification and
+ * folds its totals into the new output. Proofs meant to be chained must be
+ * saved with `--compressed`.
+ *
+ * Build the guest ELF first:
+ *   cd charm-apps/trust-game && cargo prove build
+ *
+ * Usage:
+ *   trust-game-host prove <guest-elf> <input.json> [--mock] [--compressed]
+ *                         [--previous <proof>] [--proof-out <path>]
+ *   trust-game-host execute <guest-elf> <input.json>
+ *   trust-game-host bench <guest-elf>
+ */
+
+use sp1_sdk::{
+    ExecutionReport, ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1Stdin,
+    SP1VerifyingKey,
+};
+use trust_game::encoding::CanonicalEncode;
+use trust_game::prove::{prove, PreviousProof, ProveInput, ProveOutput, CYCLE_PHASES};
+use trust_game::{GamePolicy, Move};
+
+const USAGE: &str = "Usage:
+  trust-game-host prove <guest-elf> <input.json> [--mock] [--compressed]
+                        [--previous <proof>] [--proof-out <path>]
+  trust-game-host execute <guest-elf> <input.json>
+  trust-game-host bench <guest-elf>";
+
+/// History lengths covered by `bench`
+const BENCH_MOVE_COUNTS: [usize; 4] = [10, 100, 1_000, 10_000];
+
+/// Options shared by host commands
+struct HostArgs {
+    /// Path to the compiled guest ELF
+    elf_path: String,
+    /// Path to a JSON `ProveInput`
+    input_path: String,
+    /// Use the mock prover (fast, no real proof)
+    mock: bool,
+    /// Produce a compressed proof that later sessions can verify recursively
+    compressed: bool,
+    /// Earlier proof to fold into this one, if any
+    previous: Option<String>,
+    /// Where to save the proof, if anywhere
+    proof_out: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<HostArgs, String> {
+    let mut positional = Vec::new();
+    let mut mock = false;
+    let mut compressed = false;
+    let mut previous = None;
+    let mut proof_out = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--mock" => mock = true,
+            "--compressed" => compressed = true,
+            "--previous" => {
+                previous = Some(iter.next().ok_or("--previous needs a path")?.clone());
+            }
+            "--proof-out" => {
+                proof_out = Some(iter.next().ok_or("--proof-out needs a path")?.clone());
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    match positional.as_slice() {
+        [elf_path, input_path] => Ok(HostArgs {
+            elf_path: elf_path.clone(),
+            input_path: input_path.clone(),
+            mock,
+            compressed,
+            previous,
+            proof_out,
+        }),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Load the guest ELF and JSON input from disk
+fn load(args: &HostArgs) -> Result<(Vec<u8>, ProveInput), String> {
+    let elf = read_elf(&args.elf_path)?;
+    let input_json = std::fs::read(&args.input_path)
+        .map_err(|e| format!("Failed to read input {}: {}", args.input_path, e))?;
+    let input: ProveInput = serde_json::from_slice(&input_json)
+        .map_err(|e| format!("Failed to deserialize input: {}", e))?;
+    Ok((elf, input))
+}
+
+/// Guest stdin carrying the compact binary input
+fn guest_stdin(input: &ProveInput) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write_vec(input.canonical_bytes());
+    stdin
+}
+
+/// Attach an earlier compressed proof to the input and guest stdin
+fn attach_previous(
+    path: &str,
+    vk: &SP1VerifyingKey,
+    input: &mut ProveInput,
+) -> Result<SP1Stdin, String> {
+    let previous = SP1ProofWithPublicValues::load(path)
+        .map_err(|e| format!("Failed to load previous proof {}: {}", path, e))?;
+
+    input.previous = Some(PreviousProof {
+        output: ProveOutput::from_public_values(previous.public_values.as_slice())?,
+        vkey_digest: vk.hash_u32(),
+    });
+
+    let mut stdin = guest_stdin(input);
+    match previous.proof {
+        SP1Proof::Compressed(proof) => stdin.write_proof(*proof, vk.vk.clone()),
+        _ => return Err("Previous proof must be saved with --compressed".to_string()),
+    }
+    Ok(stdin)
+}
+
+/// Prove the guest, verify the proof and check it against native execution
+fn prove_and_verify(args: &HostArgs) -> Result<ProveOutput, String> {
+    let (elf, mut input) = load(args)?;
+
+    let client = if args.mock {
+        ProverClient::mock()
+    } else {
+        ProverClient::local()
+    };
+    let (pk, vk) = client.setup(&elf);
+
+    // Previous sessions are verified by the guest against this guest's own vkey
+    let stdin = match &args.previous {
+        Some(path) => attach_previous(path, &vk, &mut input)?,
+        None => guest_stdin(&input),
+    };
+
+    // Expected output from running the same logic natively
+    let expected = prove(&input).map_err(|e| format!("Invalid game history: {}", e))?;
+
+    let mut request = client.prove(&pk, stdin);
+    if args.compressed || args.previous.is_some() {
+        request = request.compressed();
+    }
+    let proof = request.run().map_err(|e| format!("Proving failed: {}", e))?;
+
+    client
+        .verify(&proof, &vk)
+        .map_err(|e| format!("Proof verification failed: {}", e))?;
+
+    let proven = ProveOutput::from_public_values(proof.public_values.as_slice())?;
+    if proven != expected {
+        return Err("Proven output does not match native execution".to_string());
+    }
+
+    // The guest verifies previous sessions against a prover-supplied key; pin it to ours
+    proven.check_recursion_vkey(&vk.hash_u32())?;
+
+    if let Some(path) = &args.proof_out {
+        proof
+            .save(path)
+            .map_err(|e| format!("Failed to save proof to {}: {}", path, e))?;
+    }
+
+    Ok(proven)
+}
+
+/// Execute the guest without proving and return its output and cycle report
+fn execute(elf: &[u8], input: &ProveInput) -> Result<(ProveOutput, ExecutionReport), String> {
+    let client = ProverClient::mock();
+    let (public_values, report) = client
+        .execute(elf, guest_stdin(input))
+        .run()
+        .map_err(|e| format!("Execution failed: {}", e))?;
+
+    let output = ProveOutput::from_public_values(public_values.as_slice())?;
+    Ok((output, report))
+}
+
+/// Cycles spent in each phase, in `CYCLE_PHASES` order
+fn phase_cycles(report: &ExecutionReport) -> Vec<u64> {
+    CYCLE_PHASES
+        .iter()
+        .map(|phase| report.cycle_tracker.get(*phase).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Print cycle counts for a single input
+fn print_cycle_report(input: &ProveInput, report: &ExecutionReport) {
+    println!("Moves: {}", input.moves.len());
+    for (phase, cycles) in CYCLE_PHASES.iter().zip(phase_cycles(report)) {
+        println!("  {:<16} {:>12} cycles", phase, cycles);
+    }
+    println!("  {:<16} {:>12} cycles", "total", report.total_instruction_count());
+}
+
+fn defect_if(defect: bool) -> Move {
+    if defect {
+        Move::Defect
+    } else {
+        Move::Cooperate
+    }
+}
+
+/// Deterministic history of `moves` rounds: mostly cooperative with periodic defection
+fn synthetic_input(moves: usize) -> ProveInput {
+    ProveInput {
+        player_address: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+        moves: (0..moves).map(|i| defect_if(i % 5 == 4)).collect(),
+        opponent_moves: (0..moves).map(|i| defect_if(i % 7 == 6)).collect(),
+        payoffs: [2, 3, -1, 0],
+        opponent_payoffs: None,
+        matches: Vec::new(),
+        previous: None,
+        transcripts: Vec::new(),
+        game_policy: GamePolicy::default(),
+    }
+}
+
+/// Markdown table of cycle counts per phase for each benchmark history length
+fn bench(elf: &[u8]) -> Result<String, String> {
+    let mut table = format!("| moves | {} | total |\n", CYCLE_PHASES.join(" | "));
+    table.push_str(&format!("|---{}|---|\n", "|---".repeat(CYCLE_PHASES.len())));
+
+    for moves in BENCH_MOVE_COUNTS {
+        let input = synthetic_input(moves);
+        let (_, report) = execute(elf, &input)?;
+
+        let cells: Vec<String> = phase_cycles(&report).iter().map(|c| c.to_string()).collect();
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            moves,
+            cells.join(" | "),
+            report.total_instruction_count()
+        ));
+    }
+
+    Ok(table)
+}
+
+fn read_elf(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read ELF {}: {}", path, e))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (command, rest) = args.split_first().ok_or(USAGE.to_string())?;
+
+    match command.as_str() {
+        "prove" => {
+            let output = prove_and_verify(&parse_args(rest)?)?;
+            let output_json =
+                serde_json::to_string_pretty(&output).map_err(|e| e.to_string())?;
+            println!("{}", output_json);
+            Ok(())
+        }
+        "execute" => {
+            let args = parse_args(rest)?;
+            if args.previous.is_some() {
+                return Err("--previous is only supported by prove".to_string());
+            }
+            let (elf, input) = load(&args)?;
+            let (output, report) = execute(&elf, &input)?;
+
+            // Execution must agree with the native computation
+            let expected = prove(&input).map_err(|e| format!("Invalid game history: {}", e))?;
+            if output != expected {
+                return Err("Executed output does not match native execution".to_string());
+            }
+
+            print_cycle_report(&input, &report);
+            Ok(())
+        }
+        "bench" => match rest {
+            [elf_path] => {
+                print!("{}", bench(&read_elf(elf_path)?)?);
+                Ok(())
+            }
+            _ => Err(USAGE.to_string()),
+        },
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
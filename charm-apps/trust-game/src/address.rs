@@ -0,0 +1,170 @@
+/**
+ * BITCOIN ADDRESSES
+ *
+ * Segwit address encoding (BIP173 bech32 for version 0, BIP350 bech32m for
+ * version 1 and up), implemented in-crate so the zkVM guest can check that a
+ * transcript key really controls the address it signs for:
+ *
+ *   address = hrp || "1" || version || 5-bit groups of the program || checksum
+ *
+ * A taproot (P2TR) address is version 1 with the 32-byte x-only output key as
+ * its program, so the key a player signs with determines their address. The same
+ * key has one address per network, so checks always pin the expected hrp.
+ */
+
+/// Bech32 alphabet, indexed by 5-bit value
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum constant for bech32 (witness version 0)
+const BECH32_CONST: u32 = 1;
+
+/// Checksum constant for bech32m (witness version 1 and up)
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// BCH checksum over 5-bit values (BIP173)
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ *value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Human-readable part as checksummed: high bits, a zero separator, low bits
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|byte| byte >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|byte| byte & 31));
+    values
+}
+
+/// Regroup bytes into 5-bit values, zero-padding the last group
+fn to_5_bit(data: &[u8]) -> Vec<u8> {
+    let mut values = Vec::new();
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    for byte in data {
+        accumulator = ((accumulator << 8) | *byte as u32) & 0x1fff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        values.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+    values
+}
+
+/// Segwit address of a witness program, e.g. hrp "bc" (mainnet) or "tb" (testnet)
+pub fn segwit_address(hrp: &str, version: u8, program: &[u8]) -> String {
+    let mut data = vec![version];
+    data.extend(to_5_bit(program));
+
+    let constant = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0u8; 6]);
+    let checksum = polymod(&values) ^ constant;
+    for i in 0..6 {
+        data.push(((checksum >> (5 * (5 - i))) & 31) as u8);
+    }
+
+    let mut address = format!("{}1", hrp);
+    address.extend(data.iter().map(|value| CHARSET[*value as usize] as char));
+    address
+}
+
+/// Taproot address paying to a 32-byte x-only output key
+pub fn p2tr_address(hrp: &str, output_key: &[u8; 32]) -> String {
+    segwit_address(hrp, 1, output_key)
+}
+
+/// Human-readable part (network prefix) of a segwit address
+pub fn address_hrp(address: &str) -> Result<&str, String> {
+    match address.rsplit_once('1') {
+        Some((hrp, _)) if !hrp.is_empty() => Ok(hrp),
+        _ => Err(format!("{} is not a segwit address", address)),
+    }
+}
+
+/// Whether `address` is the (lowercase) P2TR address of `output_key` on network `hrp`
+pub fn is_p2tr_address(address: &str, hrp: &str, output_key: &[u8; 32]) -> bool {
+    p2tr_address(hrp, output_key) == address
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::from_hex;
+    use std::str::FromStr;
+
+    fn key(hex: &str) -> [u8; 32] {
+        from_hex(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_bip173_and_bip350_vectors() {
+        let program = from_hex("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        assert_eq!(
+            segwit_address("bc", 0, &program),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            segwit_address("tb", 0, &program),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+
+        // BIP341 wallet test vector: tweaked output key and its address
+        let output_key = key("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343");
+        assert_eq!(
+            p2tr_address("bc", &output_key),
+            "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5"
+        );
+    }
+
+    #[test]
+    fn test_p2tr_address_check() {
+        let output_key = key("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let address = p2tr_address("tb", &output_key);
+        assert!(is_p2tr_address(&address, "tb", &output_key));
+        assert_eq!(address_hrp(&address), Ok("tb"));
+
+        // The same key on another network is not accepted
+        for hrp in ["bc", "bcrt"] {
+            assert!(!is_p2tr_address(&p2tr_address(hrp, &output_key), "tb", &output_key));
+        }
+
+        // Another key, a segwit v0 address or a mangled address do not match
+        let v0 = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+        assert!(!is_p2tr_address(&address, "tb", &[1u8; 32]));
+        assert!(!is_p2tr_address(v0, "tb", &output_key));
+        assert!(!is_p2tr_address(&address.to_uppercase(), "tb", &output_key));
+        assert!(!is_p2tr_address("alice", "tb", &output_key));
+        assert!(address_hrp("alice").is_err());
+    }
+
+    #[test]
+    fn test_matches_bitcoin_crate() {
+        // An independent implementation decodes our addresses to the same scripts
+        for seed in 1..=4u8 {
+            let output_key = [seed; 32];
+            for hrp in ["bc", "tb"] {
+                let address = bitcoin::Address::from_str(&p2tr_address(hrp, &output_key))
+                    .unwrap()
+                    .assume_checked();
+                let mut script = vec![0x51, 0x20];
+                script.extend_from_slice(&output_key);
+                assert_eq!(address.script_pubkey().as_bytes(), script.as_slice());
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+/**
+ * PAYOFF MATRIX ANALYSIS
+ *
+ * Lets governance voters see what a proposed payoff change would do before
+ * voting on it. For a `PayoffMatrix` and a round count this computes:
+ * - The game class and the stage game's pure/mixed Nash equilibria
+ * - Whether Tit-for-Tat is neutrally stable against the built-in strategies
+ * - The minimum continuation probability for cooperation to be sustainable
+ * - The expected outcome of every built-in strategy matchup
+ *
+ * Matchups are computed exactly, not by sampling: every built-in strategy only
+ * looks at the opponent's previous move and whether they have ever defected, so
+ * the repeated game is a Markov chain over 20 states. The cost grows with the
+ * round count, so analyses are capped at `MAX_ANALYSIS_ROUNDS` rounds.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::mixed::{nash_equilibria, MixedStrategy};
+use crate::{get_payoffs, GameClass, Move, PayoffMatrix, Strategy};
+
+/// Tolerance for comparing expected payoffs
+const EPSILON: f64 = 1e-9;
+
+/// Most rounds a matchup is computed for; longer requests are analyzed at this length
+pub const MAX_ANALYSIS_ROUNDS: u32 = 1_000;
+
+/// Expected result of two strategies playing a repeated game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchupOutcome {
+    pub strategy_1: Strategy,
+    pub strategy_2: Strategy,
+    /// Expected total score of player 1
+    pub expected_score_1: f64,
+    /// Expected total score of player 2
+    pub expected_score_2: f64,
+    /// Expected fraction of rounds player 1 cooperates
+    pub cooperation_rate_1: f64,
+    /// Expected fraction of rounds player 2 cooperates
+    pub cooperation_rate_2: f64,
+}
+
+/// Everything voters should know about a payoff matrix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameAnalysis {
+    /// Game family (None for orderings outside the named families)
+    pub class: Option<GameClass>,
+    /// Rounds the matchups were computed for (at most `MAX_ANALYSIS_ROUNDS`)
+    pub rounds: u32,
+    /// Nash equilibria of the one-shot game, as (player 1, player 2) strategies
+    pub equilibria: Vec<(MixedStrategy, MixedStrategy)>,
+    /// Whether no built-in strategy can invade a Tit-for-Tat population
+    /// (neutral stability: mutants that tie with TFT may still drift in)
+    pub tft_neutrally_stable: bool,
+    /// Smallest probability of another round for which Tit-for-Tat cooperation
+    /// is sustainable (None if it never is)
+    pub min_continuation_probability: Option<f64>,
+    /// Every pairing of built-in strategies (each unordered pair once)
+    pub matchups: Vec<MatchupOutcome>,
+}
+
+/// Analyze a payoff matrix for games of `rounds` rounds (capped at `MAX_ANALYSIS_ROUNDS`)
+pub fn analyze(payoff_matrix: &PayoffMatrix, rounds: u32) -> GameAnalysis {
+    let rounds = rounds.min(MAX_ANALYSIS_ROUNDS);
+    let mut matchups = Vec::new();
+    for (i, strategy_1) in Strategy::ALL.iter().enumerate() {
+        for strategy_2 in &Strategy::ALL[i..] {
+            matchups.push(expected_matchup(*strategy_1, *strategy_2, payoff_matrix, rounds));
+        }
+    }
+
+    GameAnalysis {
+        class: payoff_matrix.classify(),
+        rounds,
+        equilibria: nash_equilibria(&payoff_matrix.clone().into()),
+        tft_neutrally_stable: tft_neutrally_stable(payoff_matrix, rounds),
+        min_continuation_probability: min_continuation_probability(payoff_matrix),
+        matchups,
+    }
+}
+
+/// Index of a (last moves, ever defected) state in the 20-state chain
+fn state_index(last: Option<(Move, Move)>, defected_1: bool, defected_2: bool) -> usize {
+    let last = match last {
+        None => 0,
+        Some((move_1, move_2)) => 1 + 2 * move_1 as usize + move_2 as usize,
+    };
+    last * 4 + 2 * defected_1 as usize + defected_2 as usize
+}
+
+/// Inverse of `state_index`
+fn state_from_index(index: usize) -> (Option<(Move, Move)>, bool, bool) {
+    let to_move = |bit: usize| if bit == 0 { Move::Cooperate } else { Move::Defect };
+    let last = match index / 4 {
+        0 => None,
+        code => Some((to_move((code - 1) / 2), to_move((code - 1) % 2))),
+    };
+    (last, index & 2 != 0, index & 1 != 0)
+}
+
+/// Exact expected outcome of `strategy_1` against `strategy_2` over `rounds` rounds
+/// (capped at `MAX_ANALYSIS_ROUNDS`)
+pub fn expected_matchup(
+    strategy_1: Strategy,
+    strategy_2: Strategy,
+    payoff_matrix: &PayoffMatrix,
+    rounds: u32,
+) -> MatchupOutcome {
+    let rounds = rounds.min(MAX_ANALYSIS_ROUNDS);
+    let moves = [Move::Cooperate, Move::Defect];
+
+    let mut distribution = [0.0f64; 20];
+    distribution[state_index(None, false, false)] = 1.0;
+
+    let (mut score_1, mut score_2) = (0.0, 0.0);
+    let (mut cooperations_1, mut cooperations_2) = (0.0, 0.0);
+
+    for _ in 0..rounds {
+        let mut next = [0.0f64; 20];
+
+        for (index, probability) in distribution.iter().enumerate() {
+            if *probability == 0.0 {
+                continue;
+            }
+
+            // Each player responds to the other's previous move and defection record
+            let (last, defected_1, defected_2) = state_from_index(index);
+            let response_1 = strategy_1.respond(last.map(|(_, m2)| m2), defected_2);
+            let response_2 = strategy_2.respond(last.map(|(m1, _)| m1), defected_1);
+
+            for move_1 in moves {
+                for move_2 in moves {
+                    let p = probability
+                        * response_1.probability(move_1)
+                        * response_2.probability(move_2);
+                    if p == 0.0 {
+                        continue;
+                    }
+
+                    let (payoff_1, payoff_2) = get_payoffs(move_1, move_2, payoff_matrix);
+                    score_1 += p * payoff_1 as f64;
+                    score_2 += p * payoff_2 as f64;
+                    if move_1 == Move::Cooperate {
+                        cooperations_1 += p;
+                    }
+                    if move_2 == Move::Cooperate {
+                        cooperations_2 += p;
+                    }
+
+                    next[state_index(
+                        Some((move_1, move_2)),
+                        defected_1 || move_1 == Move::Defect,
+                        defected_2 || move_2 == Move::Defect,
+                    )] += p;
+                }
+            }
+        }
+
+        distribution = next;
+    }
+
+    let rate = |cooperations: f64| if rounds == 0 { 0.0 } else { cooperations / rounds as f64 };
+
+    MatchupOutcome {
+        strategy_1,
+        strategy_2,
+        expected_score_1: score_1,
+        expected_score_2: score_2,
+        cooperation_rate_1: rate(cooperations_1),
+        cooperation_rate_2: rate(cooperations_2),
+    }
+}
+
+/// Whether a Tit-for-Tat population resists invasion by every other built-in strategy
+/// A mutant invades if it scores more against TFT than TFT does against itself, or
+/// ties and then does better against itself than TFT does against it. Mutants that
+/// tie on both (Always Cooperate and Grudge play exactly like TFT among TFT players)
+/// only drift neutrally and do not count as invading. This is neutral stability, not
+/// the strict ESS condition, which TFT never meets against such mutants.
+pub fn tft_neutrally_stable(payoff_matrix: &PayoffMatrix, rounds: u32) -> bool {
+    let score = |strategy: Strategy, against: Strategy| {
+        expected_matchup(strategy, against, payoff_matrix, rounds).expected_score_1
+    };
+
+    let tft_vs_tft = score(Strategy::TitForTat, Strategy::TitForTat);
+
+    Strategy::ALL
+        .iter()
+        .filter(|mutant| **mutant != Strategy::TitForTat)
+        .all(|mutant| {
+            let mutant_vs_tft = score(*mutant, Strategy::TitForTat);
+            if mutant_vs_tft > tft_vs_tft + EPSILON {
+                return false;
+            }
+            if mutant_vs_tft < tft_vs_tft - EPSILON {
+                return true;
+            }
+            score(*mutant, *mutant) <= score(Strategy::TitForTat, *mutant) + EPSILON
+        })
+}
+
+/// Smallest continuation probability w for which TFT cooperation is sustainable
+/// in the indefinitely repeated game: w >= max((T-R)/(T-P), (T-R)/(R-S))
+/// (Axelrod). Returns 0.0 when there is no temptation to defect, and None when
+/// mutual cooperation cannot be sustained at any w < 1.
+pub fn min_continuation_probability(payoff_matrix: &PayoffMatrix) -> Option<f64> {
+    let PayoffMatrix { r, s, t, p } = *payoff_matrix;
+
+    if t <= r {
+        return Some(0.0);
+    }
+    if r <= p || r <= s {
+        return None;
+    }
+
+    let temptation = (t - r) as f64;
+    let w = (temptation / (t - p) as f64).max(temptation / (r - s) as f64);
+    if w < 1.0 {
+        Some(w)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matchup(analysis: &GameAnalysis, s1: Strategy, s2: Strategy) -> &MatchupOutcome {
+        analysis
+            .matchups
+            .iter()
+            .find(|m| m.strategy_1 == s1 && m.strategy_2 == s2)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_state_index_roundtrip() {
+        for index in 0..20 {
+            let (last, defected_1, defected_2) = state_from_index(index);
+            assert_eq!(state_index(last, defected_1, defected_2), index);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_matchups() {
+        let analysis = analyze(&PayoffMatrix::default(), 10);
+        assert_eq!(analysis.class, Some(GameClass::PrisonersDilemma));
+        assert_eq!(analysis.matchups.len(), 15);
+
+        let tft = matchup(&analysis, Strategy::TitForTat, Strategy::TitForTat);
+        assert_eq!((tft.expected_score_1, tft.expected_score_2), (20.0, 20.0));
+        assert_eq!(tft.cooperation_rate_1, 1.0);
+
+        // Always Defect exploits TFT once, then both defect
+        let alld = matchup(&analysis, Strategy::AlwaysDefect, Strategy::TitForTat);
+        assert_eq!((alld.expected_score_1, alld.expected_score_2), (3.0, -1.0));
+        assert_eq!(alld.cooperation_rate_2, 0.1);
+    }
+
+    #[test]
+    fn test_random_matchup_expectation() {
+        // Random earns 2.5 in round 1 against TFT, then 1.0 per round on average
+        let outcome =
+            expected_matchup(Strategy::Random, Strategy::TitForTat, &PayoffMatrix::default(), 10);
+        assert!((outcome.expected_score_1 - 11.5).abs() < 1e-9);
+        assert!((outcome.cooperation_rate_1 - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tft_stability() {
+        let matrix = PayoffMatrix::default();
+
+        // A single round is a one-shot PD: defecting invades
+        assert!(!tft_neutrally_stable(&matrix, 1));
+        assert!(tft_neutrally_stable(&matrix, 10));
+
+        // A huge temptation pays off even over several rounds
+        let tempting = PayoffMatrix { r: 2, s: -1, t: 20, p: 0 };
+        assert!(!tft_neutrally_stable(&tempting, 5));
+    }
+
+    #[test]
+    fn test_rounds_capped() {
+        let analysis = analyze(&PayoffMatrix::default(), u32::MAX);
+        assert_eq!(analysis.rounds, MAX_ANALYSIS_ROUNDS);
+
+        let capped = expected_matchup(
+            Strategy::TitForTat,
+            Strategy::TitForTat,
+            &PayoffMatrix::default(),
+            u32::MAX,
+        );
+        assert!((capped.expected_score_1 - 2.0 * MAX_ANALYSIS_ROUNDS as f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_min_continuation_probability() {
+        // Default: max(1/3, 1/3)
+        let w = min_continuation_probability(&PayoffMatrix::default()).unwrap();
+        assert!((w - 1.0 / 3.0).abs() < 1e-9);
+
+        // Harmony game: nothing to resist
+        let harmony = PayoffMatrix { r: 4, s: 1, t: 3, p: 0 };
+        assert_eq!(min_continuation_probability(&harmony), Some(0.0));
+
+        // Deadlock: mutual defection beats mutual cooperation
+        let deadlock = PayoffMatrix { r: 1, s: -1, t: 3, p: 2 };
+        assert_eq!(min_continuation_probability(&deadlock), None);
+    }
+
+    #[test]
+    fn test_equilibria_in_analysis() {
+        let stag_hunt = PayoffMatrix { r: 4, s: -1, t: 3, p: 0 };
+        let analysis = analyze(&stag_hunt, 5);
+
+        assert_eq!(analysis.class, Some(GameClass::StagHunt));
+        assert_eq!(analysis.equilibria.len(), 3);
+        assert_eq!(analysis.min_continuation_probability, Some(0.0));
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg_attr(not(feature = "json-io"), no_main)]
+
+/**
+ * CHARMS ZKVM ENTRYPOINT: GOVERNANCE
+ *
+ * Second zkVM program, proving governance state transitions.
+ * The zkVM will:
+ * 1. Load the prior governance state and a batch of actions from witness data
+ * 2. Check the prior state against its committed state root
+ * 3. Verify every reputation proof attached to an action (SP1 recursion
+ *    against the trust-game vkey committed in the output)
+ * 4. Apply the actions and tally every touched proposal
+ * 5. Output the new state root and tallies, proven like reputation
+ *
+ * I/O modes match the trust-game entrypoint: SP1 guest I/O by default,
+ * JSON on stdin/stdout with the `json-io` feature.
+ *
+ * Usage (via Charms):
+ *   charms spell check --app-bins=target/release/trust-governance ...
+ */
+
+use trust_game::transition::{apply_transition, TransitionInput};
+
+#[cfg(not(feature = "json-io"))]
+sp1_zkvm::entrypoint!(main);
+
+#[cfg(not(feature = "json-io"))]
+fn main() {
+    use trust_game::encoding::sha256;
+
+    // Read input from the zkVM
+    let input: TransitionInput = sp1_zkvm::io::read();
+
+    // Verify the reputation proofs proposers and voters rely on
+    for action in &input.actions {
+        if let Some(reputation) = action.reputation_proof() {
+            let public_values_digest = sha256(&reputation.to_public_values());
+            sp1_zkvm::lib::verify::verify_sp1_proof(&input.reputation_vkey, &public_values_digest);
+        }
+    }
+
+    // Apply the batch; any invalid action aborts the proof
+    let (_, output) = apply_transition(input).expect("Invalid governance transition");
+
+    // Commit roots and tallies as public values
+    sp1_zkvm::io::commit(&output);
+}
+
+#[cfg(feature = "json-io")]
+fn main() {
+    use std::io::Read;
+
+    // Read input from stdin
+    let mut input_data = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut input_data)
+        .expect("Failed to read input");
+
+    // Deserialize input
+    let input: TransitionInput = serde_json::from_slice(&input_data)
+        .expect("Failed to deserialize input");
+
+    // Apply the batch; any invalid action aborts the proof
+    let (_, output) = apply_transition(input).expect("Invalid governance transition");
+
+    // Write output to stdout
+    let output_json = serde_json::to_vec(&output).expect("Failed to serialize output");
+    std::io::Write::write_all(&mut std::io::stdout(), &output_json)
+        .expect("Failed to write output");
+}
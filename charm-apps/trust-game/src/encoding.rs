@@ -0,0 +1,334 @@
+/**
+ * CANONICAL ENCODING
+ *
+ * Deterministic byte encoding for anything that gets hashed or committed on-chain.
+ * Two parties encoding the same value always produce identical bytes.
+ *
+ * Rules:
+ * - Integers: fixed-width big-endian (u8, u32, i32, u64)
+ * - Booleans: one byte, 0x00 or 0x01
+ * - Strings and byte strings: u32 length prefix, then raw bytes
+ * - Sequences: u32 count prefix, then elements (callers sort by key first)
+ * - Options: 0x00 for None, 0x01 followed by the value for Some
+ * - Enums: one-byte tag
+ *
+ * `Decoder` reads the same format back, for compact zkVM input/output.
+ */
+
+use sha2::{Digest, Sha256};
+
+/// Builds a canonical byte encoding
+#[derive(Debug, Default)]
+pub struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Create an empty encoder
+    pub fn new() -> Self {
+        Encoder { bytes: Vec::new() }
+    }
+
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.bytes.push(value);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn i32(&mut self, value: i32) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn i64(&mut self, value: i64) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    /// Length-prefixed byte string
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.u32(value.len() as u32);
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    /// Length-prefixed UTF-8 string
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    /// Optional u32 value
+    pub fn option_u32(&mut self, value: Option<u32>) -> &mut Self {
+        match value {
+            Some(v) => self.u8(1).u32(v),
+            None => self.u8(0),
+        }
+    }
+
+    /// Count-prefixed sequence of already-ordered items
+    pub fn seq<T: CanonicalEncode>(&mut self, items: &[&T]) -> &mut Self {
+        self.u32(items.len() as u32);
+        for item in items {
+            item.encode(self);
+        }
+        self
+    }
+
+    /// Consume the encoder and return the encoded bytes
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads values written by `Encoder`
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    /// Start decoding from the beginning of `bytes`
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Decoder { bytes, position: 0 }
+    }
+
+    /// Take the next `len` raw bytes
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("Unexpected end of input at byte {}", self.position))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    pub fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    pub fn i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    pub fn bool(&mut self) -> Result<bool, String> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(format!("Invalid boolean byte {}", other)),
+        }
+    }
+
+    /// Length-prefixed byte string
+    pub fn bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    /// Length-prefixed UTF-8 string
+    pub fn str(&mut self) -> Result<String, String> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| "Invalid UTF-8 string".to_string())
+    }
+
+    /// Optional u32 value
+    pub fn option_u32(&mut self) -> Result<Option<u32>, String> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.u32()?)),
+            other => Err(format!("Invalid option tag {}", other)),
+        }
+    }
+
+    /// 32-byte hash
+    pub fn hash(&mut self) -> Result<[u8; 32], String> {
+        self.array()
+    }
+
+    /// Ensure every byte was consumed
+    pub fn finish(self) -> Result<(), String> {
+        if self.position == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} trailing bytes after decoding",
+                self.bytes.len() - self.position
+            ))
+        }
+    }
+}
+
+/// Types with a canonical byte encoding
+pub trait CanonicalEncode {
+    /// Append this value's canonical encoding
+    fn encode(&self, encoder: &mut Encoder);
+
+    /// Canonical encoding as a standalone byte vector
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        self.encode(&mut encoder);
+        encoder.finish()
+    }
+}
+
+/// SHA-256 digest of `data`
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// SHA-256 digest of `data` prefixed with a domain-separation tag
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(tag.as_bytes());
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Lowercase hex encoding
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string (either case)
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let pairs = hex.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err("Hex string has odd length".to_string());
+    }
+
+    pairs
+        .enumerate()
+        .map(|(i, pair)| {
+            let digit = |c: u8| (c as char).to_digit(16);
+            match (digit(pair[0]), digit(pair[1])) {
+                (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                _ => Err(format!("Invalid hex at position {}", i * 2)),
+            }
+        })
+        .collect()
+}
+
+/// Decode a hex string into a 32-byte hash
+pub fn hash_from_hex(hex: &str) -> Result<[u8; 32], String> {
+    from_hex(hex)?
+        .try_into()
+        .map_err(|_| "Expected a 32-byte hash".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_width_encoding() {
+        let mut encoder = Encoder::new();
+        encoder.u8(7).u32(1).i32(-1).u64(2).bool(true).str("ab");
+
+        assert_eq!(
+            to_hex(&encoder.finish()),
+            "07\
+             00000001\
+             ffffffff\
+             0000000000000002\
+             01\
+             000000026162"
+        );
+    }
+
+    #[test]
+    fn test_option_encoding() {
+        let mut encoder = Encoder::new();
+        encoder.option_u32(None).option_u32(Some(3));
+
+        assert_eq!(encoder.finish(), vec![0, 1, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn test_decoder_roundtrip() {
+        let mut encoder = Encoder::new();
+        encoder
+            .u8(7)
+            .u32(1)
+            .i32(-1)
+            .u64(2)
+            .bool(true)
+            .str("ab")
+            .option_u32(Some(3));
+        let bytes = encoder.finish();
+
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(decoder.u8().unwrap(), 7);
+        assert_eq!(decoder.u32().unwrap(), 1);
+        assert_eq!(decoder.i32().unwrap(), -1);
+        assert_eq!(decoder.u64().unwrap(), 2);
+        assert!(decoder.bool().unwrap());
+        assert_eq!(decoder.str().unwrap(), "ab");
+        assert_eq!(decoder.option_u32().unwrap(), Some(3));
+        assert!(decoder.finish().is_ok());
+    }
+
+    #[test]
+    fn test_decoder_rejects_malformed_input() {
+        // Truncated integer
+        assert!(Decoder::new(&[0, 0, 1]).u32().is_err());
+
+        // Length prefix longer than the input
+        assert!(Decoder::new(&[0, 0, 0, 9, b'a']).str().is_err());
+
+        // Trailing bytes
+        let mut decoder = Decoder::new(&[1, 2]);
+        decoder.u8().unwrap();
+        assert!(decoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let bytes = vec![0x00, 0xab, 0xff];
+        assert_eq!(to_hex(&bytes), "00abff");
+        assert_eq!(from_hex("00ABff").unwrap(), bytes);
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("zz").is_err());
+        assert!(from_hex("é0").is_err());
+        assert!(hash_from_hex("00abff").is_err());
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            to_hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -7,10 +7,20 @@
  * - Proposals: Community members suggest rule changes (e.g., "Change R payoff to 3")
  * - Voting: Players vote on proposals, weighted by their reputation score
  * - Execution: Passed proposals update contract state for next game
+ * - Payoff changes: proposed matrices must fall in a game class the deployment's
+ *   `GamePolicy` allows (Prisoner's Dilemma only by default)
  */
 
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::{analyze, GameAnalysis};
+use crate::encoding::{tagged_hash, CanonicalEncode, Encoder};
+use crate::{GameClass, GamePolicy, PayoffMatrix};
+use crate::merkle::{hash_leaf, MerkleProof, MerkleTree, EMPTY_ROOT};
+
+/// Domain tag for governance state roots
+const STATE_ROOT_TAG: &str = "trust-game/governance-state/v1";
+
 /// Types of governance proposals
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProposalType {
@@ -33,6 +43,59 @@ pub enum Vote {
     Abstain,
 }
 
+/// How ballots on a multi-option proposal are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TallyMethod {
+    /// Voters approve any subset of options; the option with the most voting power wins
+    Approval,
+    /// Voters rank options; the weakest option is eliminated until one holds a majority
+    InstantRunoff,
+}
+
+/// Lifecycle of a proposal's bonded deposit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DepositStatus {
+    /// No deposit was bonded
+    #[default]
+    NoDeposit,
+    /// Deposit is locked while voting is open
+    Held,
+    /// Quorum was reached; deposit returned to the proposer
+    Refunded,
+    /// Quorum was missed; deposit kept by governance
+    Forfeited,
+}
+
+/// Rules limiting who may submit proposals and at what cost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalPolicy {
+    /// Minimum reputation tier required to submit a proposal
+    /// 0=Suspicious, 1=Neutral, 2=Trusted
+    pub min_proposer_tier: u8,
+    /// Maximum proposals a single address may have open for voting at once
+    pub max_active_per_address: u32,
+    /// Deposit (in satoshis) that must be bonded with each proposal (0 = optional)
+    pub min_deposit: u64,
+    /// Total voting power a multi-option proposal needs to pass, and any proposal
+    /// needs for its deposit to be refunded
+    pub quorum_voting_power: u32,
+    /// Game classes a payoff change may produce
+    #[serde(default)]
+    pub game_policy: GamePolicy,
+}
+
+impl Default for ProposalPolicy {
+    fn default() -> Self {
+        ProposalPolicy {
+            min_proposer_tier: 1,       // Neutral or better
+            max_active_per_address: 3,  // At most 3 open proposals each
+            min_deposit: 0,             // Deposits optional by default
+            quorum_voting_power: 100,   // One Trusted or two Neutral players (Neutral max is 74)
+            game_policy: GamePolicy::default(), // Prisoner's Dilemma only
+        }
+    }
+}
+
 /// A governance proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GovernanceProposal {
@@ -56,6 +119,40 @@ pub struct GovernanceProposal {
     pub abstain_voting_power: u32,
     /// Whether this proposal has been executed
     pub executed: bool,
+    /// Candidate options (empty for Yes/No proposals)
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// How option ballots are counted (None for Yes/No proposals)
+    #[serde(default)]
+    pub tally_method: Option<TallyMethod>,
+    /// Voting power behind each option after the last tally
+    /// (final runoff round for instant-runoff)
+    #[serde(default)]
+    pub option_voting_power: Vec<u32>,
+    /// Index of the winning option after the last tally
+    #[serde(default)]
+    pub winning_option: Option<u32>,
+    /// Bitcoin address of the proposer
+    #[serde(default)]
+    pub proposer: String,
+    /// Deposit bonded with this proposal (satoshis)
+    #[serde(default)]
+    pub deposit: u64,
+    /// Whether the deposit is held, refunded or forfeited
+    #[serde(default)]
+    pub deposit_status: DepositStatus,
+    /// Merkle root over the votes counted in the last tally
+    #[serde(default)]
+    pub votes_root: [u8; 32],
+    /// Proposed payoff matrix (ChangePayoff proposals only)
+    #[serde(default)]
+    pub payoff_matrix: Option<PayoffMatrix>,
+    /// Quorum for passing (multi-option) and deposit refunds, from the policy at submission
+    #[serde(default)]
+    pub quorum_voting_power: u32,
+    /// Voting power of all option ballots, each counted once (multi-option proposals only)
+    #[serde(default)]
+    pub ballot_voting_power: u32,
 }
 
 impl GovernanceProposal {
@@ -74,16 +171,63 @@ impl GovernanceProposal {
             no_voting_power: 0,
             abstain_voting_power: 0,
             executed: false,
+            options: Vec::new(),
+            tally_method: None,
+            option_voting_power: Vec::new(),
+            winning_option: None,
+            proposer: String::new(),
+            deposit: 0,
+            deposit_status: DepositStatus::NoDeposit,
+            votes_root: EMPTY_ROOT,
+            payoff_matrix: None,
+            quorum_voting_power: 0,
+            ballot_voting_power: 0,
+        }
+    }
+
+    /// Create a new proposal choosing between several options
+    pub fn new_multi_option(
+        id: u32,
+        proposal_type: ProposalType,
+        description: String,
+        options: Vec<String>,
+        tally_method: TallyMethod,
+    ) -> Self {
+        let option_count = options.len();
+        GovernanceProposal {
+            options,
+            tally_method: Some(tally_method),
+            option_voting_power: vec![0; option_count],
+            ..GovernanceProposal::new(id, proposal_type, description)
         }
     }
 
+    /// Game-theoretic analysis of the proposed payoff matrix, for voters
+    /// (None unless this proposal changes the payoff matrix; `rounds` is capped at
+    /// `MAX_ANALYSIS_ROUNDS`)
+    pub fn payoff_analysis(&self, rounds: u32) -> Option<GameAnalysis> {
+        self.payoff_matrix.as_ref().map(|matrix| analyze(matrix, rounds))
+    }
+
+    /// Check if this proposal is decided by option ballots rather than Yes/No
+    pub fn is_multi_option(&self) -> bool {
+        self.tally_method.is_some()
+    }
+
     /// Check if voting is still open
     pub fn is_voting_open(&self) -> bool {
         !self.executed && self.voting_round < self.total_voting_rounds
     }
 
     /// Check if proposal has passed (majority of voting power)
+    /// Multi-option proposals pass once quorum is reached and a single option has won the tally
     pub fn has_passed(&self) -> bool {
+        if self.is_multi_option() {
+            return self.ballot_voting_power > 0
+                && self.ballot_voting_power >= self.quorum_voting_power
+                && self.winning_option.is_some();
+        }
+
         let total_voting_power =
             self.yes_voting_power + self.no_voting_power + self.abstain_voting_power;
 
@@ -120,6 +264,38 @@ pub struct PlayerVote {
     pub timestamp: u64,
 }
 
+/// Record of a player's ballot on a multi-option proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionBallot {
+    /// Bitcoin address of the voter
+    pub address: String,
+    /// Which proposal they voted on
+    pub proposal_id: u32,
+    /// Option indices: the approved set (Approval) or a ranking,
+    /// most preferred first (InstantRunoff)
+    pub choices: Vec<u32>,
+    /// Their reputation score at time of voting
+    pub voter_reputation: u32,
+    /// Their voting power (reputation-weighted)
+    pub voting_power: u32,
+    /// Timestamp of vote
+    pub timestamp: u64,
+}
+
+/// Merkle leaf committing to a Yes/No vote
+pub fn vote_leaf(vote: &PlayerVote) -> [u8; 32] {
+    let mut data = vec![0u8];
+    data.extend_from_slice(&vote.canonical_bytes());
+    hash_leaf(&data)
+}
+
+/// Merkle leaf committing to an option ballot
+pub fn ballot_leaf(ballot: &OptionBallot) -> [u8; 32] {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&ballot.canonical_bytes());
+    hash_leaf(&data)
+}
+
 /// Voting record for a proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VotingRound {
@@ -127,6 +303,9 @@ pub struct VotingRound {
     pub proposal_id: u32,
     /// All votes cast in this round
     pub votes: Vec<PlayerVote>,
+    /// All option ballots cast in this round (multi-option proposals)
+    #[serde(default)]
+    pub ballots: Vec<OptionBallot>,
     /// Addresses that have already voted (prevent double voting)
     pub voted_addresses: Vec<String>,
 }
@@ -137,6 +316,7 @@ impl VotingRound {
         VotingRound {
             proposal_id,
             votes: Vec::new(),
+            ballots: Vec::new(),
             voted_addresses: Vec::new(),
         }
     }
@@ -146,6 +326,47 @@ impl VotingRound {
         self.voted_addresses.contains(&address.to_string())
     }
 
+    /// Merkle leaves in tree order: votes then ballots, each sorted by address
+    fn sorted_leaves(&self) -> Vec<(&str, [u8; 32])> {
+        let mut votes: Vec<&PlayerVote> = self.votes.iter().collect();
+        votes.sort_by(|a, b| a.address.cmp(&b.address));
+
+        let mut ballots: Vec<&OptionBallot> = self.ballots.iter().collect();
+        ballots.sort_by(|a, b| a.address.cmp(&b.address));
+
+        votes
+            .into_iter()
+            .map(|v| (v.address.as_str(), vote_leaf(v)))
+            .chain(ballots.into_iter().map(|b| (b.address.as_str(), ballot_leaf(b))))
+            .collect()
+    }
+
+    /// Merkle tree over every vote and ballot in this round
+    pub fn merkle_tree(&self) -> MerkleTree {
+        MerkleTree::new(self.sorted_leaves().into_iter().map(|(_, leaf)| leaf).collect())
+    }
+
+    /// Merkle root over every vote and ballot in this round
+    pub fn votes_root(&self) -> [u8; 32] {
+        self.merkle_tree().root()
+    }
+
+    /// Inclusion proof for the vote or ballot cast by `address`
+    pub fn inclusion_proof(&self, address: &str) -> Option<MerkleProof> {
+        let index = self
+            .sorted_leaves()
+            .iter()
+            .position(|(voter, _)| *voter == address)?;
+        self.merkle_tree().proof(index)
+    }
+
+    /// Total voting power cast in this round (votes and option ballots)
+    pub fn total_voting_power(&self) -> u32 {
+        let vote_power: u32 = self.votes.iter().map(|v| v.voting_power).sum();
+        let ballot_power: u32 = self.ballots.iter().map(|b| b.voting_power).sum();
+        vote_power + ballot_power
+    }
+
     /// Record a vote (prevents double voting)
     pub fn cast_vote(
         &mut self,
@@ -182,6 +403,8 @@ impl VotingRound {
             return Err("Proposal already executed".to_string());
         }
 
+        proposal.votes_root = self.votes_root();
+
         // Reset vote counts
         proposal.yes_votes = 0;
         proposal.no_votes = 0;
@@ -210,6 +433,175 @@ impl VotingRound {
 
         Ok(())
     }
+
+    /// Record an option ballot (shares double-vote prevention with `cast_vote`)
+    pub fn cast_ballot(
+        &mut self,
+        address: String,
+        choices: Vec<u32>,
+        option_count: usize,
+        voter_reputation: u32,
+        voting_power: u32,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        // Check if already voted
+        if self.has_voted(&address) {
+            return Err(format!("Player {} has already voted", address));
+        }
+
+        // Validate choices
+        if choices.is_empty() {
+            return Err("Ballot must choose at least one option".to_string());
+        }
+        for (i, choice) in choices.iter().enumerate() {
+            if *choice as usize >= option_count {
+                return Err(format!("Option {} does not exist", choice));
+            }
+            if choices[..i].contains(choice) {
+                return Err(format!("Option {} chosen more than once", choice));
+            }
+        }
+
+        // Record the ballot
+        self.ballots.push(OptionBallot {
+            address: address.clone(),
+            proposal_id: self.proposal_id,
+            choices,
+            voter_reputation,
+            voting_power,
+            timestamp,
+        });
+
+        // Add to voted addresses
+        self.voted_addresses.push(address);
+
+        Ok(())
+    }
+
+    /// Tally option ballots and update a multi-option proposal
+    pub fn tally_options(&self, proposal: &mut GovernanceProposal) -> Result<(), String> {
+        if proposal.executed {
+            return Err("Proposal already executed".to_string());
+        }
+
+        let method = proposal
+            .tally_method
+            .ok_or("Proposal is not multi-option".to_string())?;
+
+        let (option_voting_power, winning_option) = match method {
+            TallyMethod::Approval => tally_approval(&self.ballots, proposal.options.len()),
+            TallyMethod::InstantRunoff => {
+                tally_instant_runoff(&self.ballots, proposal.options.len())
+            }
+        };
+
+        proposal.option_voting_power = option_voting_power;
+        proposal.winning_option = winning_option;
+        proposal.ballot_voting_power = self.ballots.iter().map(|b| b.voting_power).sum();
+        proposal.votes_root = self.votes_root();
+
+        Ok(())
+    }
+}
+
+/// Index of the single option holding the most voting power (None on a tie or no votes)
+fn unique_leader(option_voting_power: &[u32], eligible: &[bool]) -> Option<u32> {
+    let mut leader: Option<usize> = None;
+    let mut tied = false;
+
+    for (index, power) in option_voting_power.iter().enumerate() {
+        if !eligible[index] || *power == 0 {
+            continue;
+        }
+        match leader {
+            Some(current) if *power < option_voting_power[current] => {}
+            Some(current) if *power == option_voting_power[current] => tied = true,
+            _ => {
+                leader = Some(index);
+                tied = false;
+            }
+        }
+    }
+
+    if tied {
+        None
+    } else {
+        leader.map(|index| index as u32)
+    }
+}
+
+/// Approval tally: each ballot adds its voting power to every approved option
+fn tally_approval(ballots: &[OptionBallot], option_count: usize) -> (Vec<u32>, Option<u32>) {
+    let mut option_voting_power = vec![0u32; option_count];
+
+    for ballot in ballots {
+        for choice in &ballot.choices {
+            option_voting_power[*choice as usize] += ballot.voting_power;
+        }
+    }
+
+    let winner = unique_leader(&option_voting_power, &vec![true; option_count]);
+    (option_voting_power, winner)
+}
+
+/// Instant-runoff tally: repeatedly eliminate the weakest option until one
+/// holds a majority of the voting power on non-exhausted ballots
+///
+/// Ties for weakest are broken by the earlier rounds' counts, most recent round
+/// first: the option that was weaker in the latest round where the tied options
+/// differ is eliminated. Options tied in every round are eliminated together;
+/// if that would eliminate every remaining option the tally is a tie (no winner).
+fn tally_instant_runoff(
+    ballots: &[OptionBallot],
+    option_count: usize,
+) -> (Vec<u32>, Option<u32>) {
+    let mut continuing = vec![true; option_count];
+    let mut earlier_rounds: Vec<Vec<u32>> = Vec::new();
+
+    loop {
+        // Count each ballot toward its highest-ranked continuing option
+        let mut option_voting_power = vec![0u32; option_count];
+        for ballot in ballots {
+            if let Some(choice) = ballot
+                .choices
+                .iter()
+                .find(|choice| continuing[**choice as usize])
+            {
+                option_voting_power[*choice as usize] += ballot.voting_power;
+            }
+        }
+
+        let active_power: u32 = option_voting_power.iter().sum();
+        if active_power == 0 {
+            return (option_voting_power, None);
+        }
+
+        // Majority of active voting power wins
+        if let Some(leader) = unique_leader(&option_voting_power, &continuing) {
+            if option_voting_power[leader as usize] > active_power / 2 {
+                return (option_voting_power, Some(leader));
+            }
+        }
+
+        // Weakest continuing options, narrowed by earlier rounds (most recent first)
+        earlier_rounds.push(option_voting_power.clone());
+        let mut weakest: Vec<usize> = (0..option_count).filter(|i| continuing[*i]).collect();
+        for counts in earlier_rounds.iter().rev() {
+            let lowest = weakest.iter().map(|i| counts[*i]).min().unwrap_or(0);
+            weakest.retain(|i| counts[*i] == lowest);
+            if weakest.len() == 1 {
+                break;
+            }
+        }
+
+        let remaining = continuing.iter().filter(|c| **c).count();
+        if weakest.is_empty() || weakest.len() >= remaining {
+            return (option_voting_power, None);
+        }
+        for index in weakest {
+            continuing[index] = false;
+        }
+    }
 }
 
 /// Cross-app dependency registration
@@ -238,37 +630,214 @@ pub struct GovernanceState {
     pub voting_rounds: Vec<VotingRound>,
     /// Apps that depend on this reputation system
     pub dependent_apps: Vec<DependentApp>,
+    /// Proposal submission rules (tier, rate limit, deposit, quorum)
+    #[serde(default)]
+    pub policy: ProposalPolicy,
+    /// Total deposits forfeited by proposals that missed quorum (satoshis)
+    #[serde(default)]
+    pub forfeited_deposits: u64,
 }
 
 impl GovernanceState {
     /// Create new governance state
     pub fn new() -> Self {
+        Self::new_with_policy(ProposalPolicy::default())
+    }
+
+    /// Create new governance state with custom proposal rules
+    pub fn new_with_policy(policy: ProposalPolicy) -> Self {
         GovernanceState {
             next_proposal_id: 1,
             proposals: Vec::new(),
             voting_rounds: Vec::new(),
             dependent_apps: Vec::new(),
+            policy,
+            forfeited_deposits: 0,
         }
     }
 
-    /// Create a new proposal
-    pub fn create_proposal(
+    /// Count proposals from an address that are still open for voting
+    pub fn active_proposal_count(&self, proposer: &str) -> u32 {
+        self.proposals
+            .iter()
+            .filter(|p| p.proposer == proposer && p.is_voting_open())
+            .count() as u32
+    }
+
+    /// Check a proposer against the submission policy
+    fn check_proposer(
+        &self,
+        proposer: &str,
+        proposer_tier: u8,
+        deposit: u64,
+    ) -> Result<(), String> {
+        if proposer_tier < self.policy.min_proposer_tier {
+            return Err(format!(
+                "Proposer tier {} is below minimum tier {}",
+                proposer_tier, self.policy.min_proposer_tier
+            ));
+        }
+
+        if self.active_proposal_count(proposer) >= self.policy.max_active_per_address {
+            return Err(format!(
+                "Player {} already has {} active proposals",
+                proposer, self.policy.max_active_per_address
+            ));
+        }
+
+        if deposit < self.policy.min_deposit {
+            return Err(format!(
+                "Deposit of {} sats is below minimum of {} sats",
+                deposit, self.policy.min_deposit
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Assign an ID to a vetted proposal and open its voting round
+    fn submit(
         &mut self,
-        proposal_type: ProposalType,
-        description: String,
+        mut proposal: GovernanceProposal,
+        proposer: String,
+        deposit: u64,
     ) -> u32 {
         let id = self.next_proposal_id;
         self.next_proposal_id += 1;
 
-        let proposal = GovernanceProposal::new(id, proposal_type, description);
-        let voting_round = VotingRound::new(id);
+        proposal.id = id;
+        proposal.proposer = proposer;
+        proposal.deposit = deposit;
+        proposal.quorum_voting_power = self.policy.quorum_voting_power;
+        proposal.deposit_status = if deposit > 0 {
+            DepositStatus::Held
+        } else {
+            DepositStatus::NoDeposit
+        };
 
         self.proposals.push(proposal);
-        self.voting_rounds.push(voting_round);
+        self.voting_rounds.push(VotingRound::new(id));
 
         id
     }
 
+    /// Create a proposal bypassing the submission policy, for tests
+    /// Players submit through `create_player_proposal`
+    #[cfg(test)]
+    fn create_proposal(
+        &mut self,
+        proposal_type: ProposalType,
+        description: String,
+    ) -> u32 {
+        let proposal = GovernanceProposal::new(0, proposal_type, description);
+        self.submit(proposal, String::new(), 0)
+    }
+
+    /// Create a new proposal on behalf of a player
+    /// The proposer must meet the policy's tier, rate limit and deposit rules
+    pub fn create_player_proposal(
+        &mut self,
+        proposer: String,
+        proposer_tier: u8,
+        proposal_type: ProposalType,
+        description: String,
+        deposit: u64,
+    ) -> Result<u32, String> {
+        self.check_proposer(&proposer, proposer_tier, deposit)?;
+
+        let proposal = GovernanceProposal::new(0, proposal_type, description);
+        Ok(self.submit(proposal, proposer, deposit))
+    }
+
+    /// Create a proposal to change the payoff matrix
+    /// The new matrix must classify as a game the policy's `GamePolicy` allows
+    pub fn create_payoff_proposal(
+        &mut self,
+        proposer: String,
+        proposer_tier: u8,
+        description: String,
+        payoff_matrix: PayoffMatrix,
+        deposit: u64,
+    ) -> Result<u32, String> {
+        self.check_payoff_matrix(&payoff_matrix)?;
+        self.check_proposer(&proposer, proposer_tier, deposit)?;
+
+        let proposal = GovernanceProposal {
+            payoff_matrix: Some(payoff_matrix),
+            ..GovernanceProposal::new(0, ProposalType::ChangePayoff, description)
+        };
+        Ok(self.submit(proposal, proposer, deposit))
+    }
+
+    /// Check a payoff matrix against this deployment's game policy
+    pub fn check_payoff_matrix(&self, payoff_matrix: &PayoffMatrix) -> Result<GameClass, String> {
+        self.policy.game_policy.check(payoff_matrix)
+    }
+
+    /// Create a new proposal choosing between several options
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_multi_option_proposal(
+        &mut self,
+        proposer: String,
+        proposer_tier: u8,
+        proposal_type: ProposalType,
+        description: String,
+        options: Vec<String>,
+        tally_method: TallyMethod,
+        deposit: u64,
+    ) -> Result<u32, String> {
+        if options.len() < 2 {
+            return Err("Multi-option proposal needs at least 2 options".to_string());
+        }
+
+        self.check_proposer(&proposer, proposer_tier, deposit)?;
+
+        let proposal = GovernanceProposal::new_multi_option(
+            0,
+            proposal_type,
+            description,
+            options,
+            tally_method,
+        );
+        Ok(self.submit(proposal, proposer, deposit))
+    }
+
+    /// Settle a proposal's deposit once voting has closed
+    /// Refunded if the proposal reached the quorum it was submitted under, forfeited otherwise
+    pub fn settle_deposit(&mut self, proposal_id: u32) -> Result<DepositStatus, String> {
+        let total_voting_power = self
+            .voting_rounds
+            .iter()
+            .find(|vr| vr.proposal_id == proposal_id)
+            .map(|vr| vr.total_voting_power())
+            .unwrap_or(0);
+
+        let proposal = self
+            .get_proposal_mut(proposal_id)
+            .ok_or("Proposal not found".to_string())?;
+
+        if proposal.is_voting_open() {
+            return Err("Voting period has not ended".to_string());
+        }
+
+        if proposal.deposit_status != DepositStatus::Held {
+            return Err("No deposit held for proposal".to_string());
+        }
+
+        proposal.deposit_status = if total_voting_power >= proposal.quorum_voting_power {
+            DepositStatus::Refunded
+        } else {
+            DepositStatus::Forfeited
+        };
+
+        let status = proposal.deposit_status;
+        if status == DepositStatus::Forfeited {
+            self.forfeited_deposits += proposal.deposit;
+        }
+
+        Ok(status)
+    }
+
     /// Get a proposal by ID
     pub fn get_proposal(&self, id: u32) -> Option<&GovernanceProposal> {
         self.proposals.iter().find(|p| p.id == id)
@@ -306,6 +875,10 @@ impl GovernanceState {
             return Err("Voting period has ended".to_string());
         }
 
+        if self.get_proposal(proposal_id).is_some_and(|p| p.is_multi_option()) {
+            return Err("Proposal requires an option ballot".to_string());
+        }
+
         // Cast vote in voting round
         if let Some(voting_round) = self.get_voting_round_mut(proposal_id) {
             voting_round.cast_vote(
@@ -320,7 +893,10 @@ impl GovernanceState {
         // Tally votes - get copy of votes first to avoid borrow issues
         if let Some(vr) = self.voting_rounds.iter().find(|v| v.proposal_id == proposal_id) {
             let votes_copy = vr.votes.clone();
+            let votes_root = vr.votes_root();
             if let Some(proposal) = self.get_proposal_mut(proposal_id) {
+                proposal.votes_root = votes_root;
+
                 // Manually tally
                 proposal.yes_votes = 0;
                 proposal.no_votes = 0;
@@ -351,6 +927,67 @@ impl GovernanceState {
         Ok(())
     }
 
+    /// Cast an option ballot on a multi-option proposal
+    pub fn vote_options(
+        &mut self,
+        proposal_id: u32,
+        address: String,
+        choices: Vec<u32>,
+        voter_reputation: u32,
+        voting_power: u32,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        // Check if proposal exists, is multi-option and voting is open
+        let proposal = self
+            .get_proposal(proposal_id)
+            .ok_or("Proposal not found".to_string())?;
+
+        if !proposal.is_multi_option() {
+            return Err("Proposal requires a Yes/No vote".to_string());
+        }
+
+        if !proposal.is_voting_open() {
+            return Err("Voting period has ended".to_string());
+        }
+
+        let option_count = proposal.options.len();
+
+        // Cast ballot in voting round
+        let voting_round = self
+            .get_voting_round_mut(proposal_id)
+            .ok_or("Voting round not found".to_string())?;
+        voting_round.cast_ballot(
+            address,
+            choices,
+            option_count,
+            voter_reputation,
+            voting_power,
+            timestamp,
+        )?;
+
+        // Re-tally with the updated ballots
+        let voting_round = voting_round.clone();
+        if let Some(proposal) = self.get_proposal_mut(proposal_id) {
+            voting_round.tally_options(proposal)?;
+        }
+
+        Ok(())
+    }
+
+    /// Advance a proposal to its next voting round
+    pub fn advance_voting_round(&mut self, proposal_id: u32) -> Result<(), String> {
+        let proposal = self
+            .get_proposal_mut(proposal_id)
+            .ok_or("Proposal not found".to_string())?;
+
+        if !proposal.is_voting_open() {
+            return Err("Voting period has ended".to_string());
+        }
+
+        proposal.advance_round();
+        Ok(())
+    }
+
     /// Execute a proposal (mark as executed)
     pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<bool, String> {
         let proposal = self
@@ -415,6 +1052,12 @@ impl GovernanceState {
         self.dependent_apps.iter().collect()
     }
 
+    /// SHA-256 commitment to the whole governance state
+    /// Computed over the canonical encoding, so it is independent of vote order
+    pub fn state_root(&self) -> [u8; 32] {
+        tagged_hash(STATE_ROOT_TAG, &self.canonical_bytes())
+    }
+
     /// Check if an address meets minimum reputation for an app
     pub fn check_app_eligibility(
         &self,
@@ -431,9 +1074,196 @@ impl GovernanceState {
     }
 }
 
+// Canonical encoding: collections are sorted by their key so the encoding
+// (and therefore `state_root`) does not depend on insertion order.
+
+impl CanonicalEncode for ProposalType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u8(match self {
+            ProposalType::ChangePayoff => 0,
+            ProposalType::AddStrategy => 1,
+            ProposalType::ChangeGovernance => 2,
+        });
+    }
+}
+
+impl CanonicalEncode for Vote {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u8(match self {
+            Vote::Yes => 0,
+            Vote::No => 1,
+            Vote::Abstain => 2,
+        });
+    }
+}
+
+impl CanonicalEncode for TallyMethod {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u8(match self {
+            TallyMethod::Approval => 0,
+            TallyMethod::InstantRunoff => 1,
+        });
+    }
+}
+
+impl CanonicalEncode for DepositStatus {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u8(match self {
+            DepositStatus::NoDeposit => 0,
+            DepositStatus::Held => 1,
+            DepositStatus::Refunded => 2,
+            DepositStatus::Forfeited => 3,
+        });
+    }
+}
+
+impl CanonicalEncode for ProposalPolicy {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder
+            .u8(self.min_proposer_tier)
+            .u32(self.max_active_per_address)
+            .u64(self.min_deposit)
+            .u32(self.quorum_voting_power);
+        self.game_policy.encode(encoder);
+    }
+}
+
+impl CanonicalEncode for GovernanceProposal {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u32(self.id);
+        self.proposal_type.encode(encoder);
+        encoder
+            .str(&self.description)
+            .u32(self.voting_round)
+            .u32(self.total_voting_rounds)
+            .u32(self.yes_votes)
+            .u32(self.no_votes)
+            .u32(self.abstain_votes)
+            .u32(self.yes_voting_power)
+            .u32(self.no_voting_power)
+            .u32(self.abstain_voting_power)
+            .bool(self.executed);
+
+        encoder.u32(self.options.len() as u32);
+        for option in &self.options {
+            encoder.str(option);
+        }
+        match &self.tally_method {
+            Some(method) => {
+                encoder.u8(1);
+                method.encode(encoder);
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+        encoder.u32(self.option_voting_power.len() as u32);
+        for power in &self.option_voting_power {
+            encoder.u32(*power);
+        }
+        encoder.option_u32(self.winning_option);
+
+        encoder.str(&self.proposer).u64(self.deposit);
+        self.deposit_status.encode(encoder);
+        encoder.bytes(&self.votes_root);
+
+        match &self.payoff_matrix {
+            Some(matrix) => {
+                encoder.u8(1);
+                matrix.encode(encoder);
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+
+        encoder
+            .u32(self.quorum_voting_power)
+            .u32(self.ballot_voting_power);
+    }
+}
+
+impl CanonicalEncode for PlayerVote {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.address).u32(self.proposal_id);
+        self.vote.encode(encoder);
+        encoder
+            .u32(self.voter_reputation)
+            .u32(self.voting_power)
+            .u64(self.timestamp);
+    }
+}
+
+impl CanonicalEncode for OptionBallot {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.address).u32(self.proposal_id);
+        encoder.u32(self.choices.len() as u32);
+        for choice in &self.choices {
+            encoder.u32(*choice);
+        }
+        encoder
+            .u32(self.voter_reputation)
+            .u32(self.voting_power)
+            .u64(self.timestamp);
+    }
+}
+
+impl CanonicalEncode for VotingRound {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u32(self.proposal_id);
+
+        let mut votes: Vec<&PlayerVote> = self.votes.iter().collect();
+        votes.sort_by(|a, b| a.address.cmp(&b.address));
+        encoder.seq(&votes);
+
+        let mut ballots: Vec<&OptionBallot> = self.ballots.iter().collect();
+        ballots.sort_by(|a, b| a.address.cmp(&b.address));
+        encoder.seq(&ballots);
+
+        let mut voted_addresses: Vec<&String> = self.voted_addresses.iter().collect();
+        voted_addresses.sort();
+        encoder.u32(voted_addresses.len() as u32);
+        for address in voted_addresses {
+            encoder.str(address);
+        }
+    }
+}
+
+impl CanonicalEncode for DependentApp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder
+            .str(&self.app_id)
+            .str(&self.app_name)
+            .u8(self.min_reputation_tier)
+            .u64(self.registered_at);
+    }
+}
+
+impl CanonicalEncode for GovernanceState {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u32(self.next_proposal_id);
+
+        let mut proposals: Vec<&GovernanceProposal> = self.proposals.iter().collect();
+        proposals.sort_by_key(|p| p.id);
+        encoder.seq(&proposals);
+
+        let mut voting_rounds: Vec<&VotingRound> = self.voting_rounds.iter().collect();
+        voting_rounds.sort_by_key(|vr| vr.proposal_id);
+        encoder.seq(&voting_rounds);
+
+        let mut dependent_apps: Vec<&DependentApp> = self.dependent_apps.iter().collect();
+        dependent_apps.sort_by(|a, b| a.app_id.cmp(&b.app_id));
+        encoder.seq(&dependent_apps);
+
+        self.policy.encode(encoder);
+        encoder.u64(self.forfeited_deposits);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::MAX_ANALYSIS_ROUNDS;
 
     #[test]
     fn test_proposal_creation() {
@@ -594,4 +1424,532 @@ mod tests {
         assert_eq!(apps[0].app_name, "NFT");
         assert_eq!(apps[1].app_name, "DeFi");
     }
+
+    #[test]
+    fn test_approval_voting() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Next season payoff matrix".to_string(),
+                vec!["Classic".to_string(), "High reward".to_string(), "Harsh".to_string()],
+                TallyMethod::Approval,
+                0,
+            )
+            .unwrap();
+
+        gov.vote_options(id, "alice".to_string(), vec![0, 1], 75, 112, 1000)
+            .unwrap();
+        gov.vote_options(id, "bob".to_string(), vec![1], 60, 60, 1001)
+            .unwrap();
+        gov.vote_options(id, "carol".to_string(), vec![2], 90, 135, 1002)
+            .unwrap();
+
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.option_voting_power, vec![112, 172, 135]);
+        assert_eq!(proposal.winning_option, Some(1));
+        assert!(proposal.has_passed());
+    }
+
+    #[test]
+    fn test_instant_runoff_voting() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Next season payoff matrix".to_string(),
+                vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                TallyMethod::InstantRunoff,
+                0,
+            )
+            .unwrap();
+
+        // First preferences: A=100, B=80, C=40 -> no majority, C eliminated
+        gov.vote_options(id, "alice".to_string(), vec![0, 1], 80, 100, 1000)
+            .unwrap();
+        gov.vote_options(id, "bob".to_string(), vec![1, 0], 70, 80, 1001)
+            .unwrap();
+        gov.vote_options(id, "carol".to_string(), vec![2, 1], 40, 40, 1002)
+            .unwrap();
+
+        // Carol's ballot transfers to B: A=100, B=120
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.option_voting_power, vec![100, 120, 0]);
+        assert_eq!(proposal.winning_option, Some(1));
+    }
+
+    #[test]
+    fn test_option_ballot_validation() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Pick one".to_string(),
+                vec!["A".to_string(), "B".to_string()],
+                TallyMethod::InstantRunoff,
+                0,
+            )
+            .unwrap();
+
+        // Unknown option, duplicate ranking, empty ballot
+        assert!(gov.vote_options(id, "alice".to_string(), vec![2], 75, 112, 1000).is_err());
+        assert!(gov.vote_options(id, "alice".to_string(), vec![0, 0], 75, 112, 1000).is_err());
+        assert!(gov.vote_options(id, "alice".to_string(), vec![], 75, 112, 1000).is_err());
+
+        // Yes/No vote is rejected on a multi-option proposal
+        assert!(gov.vote(id, "alice".to_string(), Vote::Yes, 75, 112, 1000).is_err());
+
+        // Double ballot is rejected
+        gov.vote_options(id, "alice".to_string(), vec![0], 75, 112, 1000)
+            .unwrap();
+        assert!(gov.vote_options(id, "alice".to_string(), vec![1], 75, 112, 1001).is_err());
+
+        // Fewer than 2 options is rejected
+        assert!(gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Only one".to_string(),
+                vec!["A".to_string()],
+                TallyMethod::Approval,
+                0,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_option_tie_has_no_winner() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::AddStrategy,
+                "Which strategy to add".to_string(),
+                vec!["Pavlov".to_string(), "Detective".to_string()],
+                TallyMethod::Approval,
+                0,
+            )
+            .unwrap();
+
+        gov.vote_options(id, "alice".to_string(), vec![0], 60, 60, 1000)
+            .unwrap();
+        gov.vote_options(id, "bob".to_string(), vec![1], 60, 60, 1001)
+            .unwrap();
+
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.winning_option, None);
+        assert!(!proposal.has_passed());
+    }
+
+    #[test]
+    fn test_instant_runoff_tie_break() {
+        let ballot = |address: &str, choices: Vec<u32>, voting_power: u32| OptionBallot {
+            address: address.to_string(),
+            proposal_id: 1,
+            choices,
+            voter_reputation: voting_power,
+            voting_power,
+            timestamp: 0,
+        };
+
+        // Round 1: A=50, B=30, C=30, D=20 -> D eliminated, its ballot goes to C
+        // Round 2: A=50, B=30, C=50 -> B is weakest; no tie
+        // Round 3: B's ballot goes to A: A=80, C=50 -> A wins
+        let ballots = vec![
+            ballot("a", vec![0], 50),
+            ballot("b", vec![1, 0], 30),
+            ballot("c", vec![2], 30),
+            ballot("d", vec![3, 2], 20),
+        ];
+        assert_eq!(tally_instant_runoff(&ballots, 4).1, Some(0));
+
+        // Round 1: A=40, B=35, C=25 -> C eliminated, its ballot goes to B
+        // Round 2: A=40, B=60 -> B wins
+        let ballots = vec![
+            ballot("a", vec![0], 40),
+            ballot("b", vec![1], 35),
+            ballot("c", vec![2, 1], 25),
+        ];
+        assert_eq!(tally_instant_runoff(&ballots, 3).1, Some(1));
+
+        // B and C tie at 30 in round 2, but C had less in round 1: C is eliminated
+        // Round 1: A=45, B=30, C=25, D=5 -> D eliminated, its ballot goes to C
+        // Round 2: A=45, B=30, C=30 -> C eliminated (25 < 30 in round 1)
+        // Round 3: C's ballots go to B: A=45, B=60 -> B wins
+        let ballots = vec![
+            ballot("a", vec![0], 45),
+            ballot("b", vec![1], 30),
+            ballot("c", vec![2, 1], 25),
+            ballot("d", vec![3, 2, 1], 5),
+        ];
+        assert_eq!(tally_instant_runoff(&ballots, 4).1, Some(1));
+
+        // Tied in every round: no arbitrary winner
+        let ballots = vec![ballot("a", vec![0], 40), ballot("b", vec![1], 40)];
+        let (power, winner) = tally_instant_runoff(&ballots, 2);
+        assert_eq!(power, vec![40, 40]);
+        assert_eq!(winner, None);
+    }
+
+    #[test]
+    fn test_multi_option_quorum() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::AddStrategy,
+                "Which strategy to add".to_string(),
+                vec!["Pavlov".to_string(), "Detective".to_string()],
+                TallyMethod::Approval,
+                0,
+            )
+            .unwrap();
+
+        // A single small ballot wins the tally but misses the quorum of 100
+        gov.vote_options(id, "alice".to_string(), vec![0], 40, 40, 1000)
+            .unwrap();
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.winning_option, Some(0));
+        assert!(!proposal.has_passed());
+
+        // Approving several options does not count toward quorum more than once
+        gov.vote_options(id, "bob".to_string(), vec![0, 1], 40, 40, 1001)
+            .unwrap();
+        assert!(!gov.get_proposal(id).unwrap().has_passed());
+
+        gov.vote_options(id, "dave".to_string(), vec![0], 30, 30, 1002)
+            .unwrap();
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.ballot_voting_power, 110);
+        assert!(proposal.has_passed());
+
+        // Yes/No proposals are decided by majority alone
+        let id = gov
+            .create_player_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                0,
+            )
+            .unwrap();
+        gov.vote(id, "alice".to_string(), Vote::Yes, 60, 60, 1003)
+            .unwrap();
+        assert!(gov.get_proposal(id).unwrap().has_passed());
+    }
+
+    #[test]
+    fn test_proposer_tier_requirement() {
+        let mut gov = GovernanceState::new();
+
+        // Suspicious players cannot submit under the default policy
+        let result = gov.create_player_proposal(
+            "mallory".to_string(),
+            0,
+            ProposalType::ChangePayoff,
+            "Change T to 10".to_string(),
+            0,
+        );
+        assert!(result.is_err());
+        assert!(gov.proposals.is_empty());
+    }
+
+    #[test]
+    fn test_active_proposal_limit() {
+        let mut gov = GovernanceState::new_with_policy(ProposalPolicy {
+            max_active_per_address: 2,
+            ..ProposalPolicy::default()
+        });
+
+        for i in 0..2 {
+            gov.create_player_proposal(
+                "alice".to_string(),
+                1,
+                ProposalType::ChangePayoff,
+                format!("Proposal {}", i),
+                0,
+            )
+            .unwrap();
+        }
+
+        // Third concurrent proposal is rejected
+        assert!(gov
+            .create_player_proposal(
+                "alice".to_string(),
+                1,
+                ProposalType::ChangePayoff,
+                "Proposal 2".to_string(),
+                0,
+            )
+            .is_err());
+
+        // Once one closes, alice may submit again
+        let first = gov.get_proposal_mut(1).unwrap();
+        first.voting_round = first.total_voting_rounds;
+        assert_eq!(gov.active_proposal_count("alice"), 1);
+        assert!(gov
+            .create_player_proposal(
+                "alice".to_string(),
+                1,
+                ProposalType::ChangePayoff,
+                "Proposal 2".to_string(),
+                0,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_deposit_refunded_on_quorum() {
+        let mut gov = GovernanceState::new_with_policy(ProposalPolicy {
+            min_deposit: 10_000,
+            quorum_voting_power: 100,
+            ..ProposalPolicy::default()
+        });
+
+        // Deposit below minimum is rejected
+        assert!(gov
+            .create_player_proposal(
+                "alice".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                5_000,
+            )
+            .is_err());
+
+        let id = gov
+            .create_player_proposal(
+                "alice".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                10_000,
+            )
+            .unwrap();
+        assert_eq!(gov.get_proposal(id).unwrap().deposit_status, DepositStatus::Held);
+
+        gov.vote(id, "bob".to_string(), Vote::No, 80, 120, 1000)
+            .unwrap();
+
+        // Cannot settle while voting is open
+        assert!(gov.settle_deposit(id).is_err());
+
+        let proposal = gov.get_proposal_mut(id).unwrap();
+        proposal.voting_round = proposal.total_voting_rounds;
+
+        assert_eq!(gov.settle_deposit(id).unwrap(), DepositStatus::Refunded);
+        assert_eq!(gov.forfeited_deposits, 0);
+
+        // Cannot settle twice
+        assert!(gov.settle_deposit(id).is_err());
+    }
+
+    #[test]
+    fn test_deposit_forfeited_without_quorum() {
+        let mut gov = GovernanceState::new_with_policy(ProposalPolicy {
+            min_deposit: 10_000,
+            quorum_voting_power: 100,
+            ..ProposalPolicy::default()
+        });
+
+        let id = gov
+            .create_player_proposal(
+                "alice".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                10_000,
+            )
+            .unwrap();
+
+        gov.vote(id, "bob".to_string(), Vote::Yes, 40, 20, 1000)
+            .unwrap();
+
+        let proposal = gov.get_proposal_mut(id).unwrap();
+        proposal.voting_round = proposal.total_voting_rounds;
+
+        // Settled against the quorum the proposal was submitted under
+        gov.policy.quorum_voting_power = 10;
+        assert_eq!(gov.settle_deposit(id).unwrap(), DepositStatus::Forfeited);
+        assert_eq!(gov.forfeited_deposits, 10_000);
+    }
+
+    #[test]
+    fn test_payoff_proposal_game_policy() {
+        let stag_hunt = PayoffMatrix { r: 4, s: -1, t: 3, p: 0 };
+
+        // Default policy: Prisoner's Dilemma only
+        let mut gov = GovernanceState::new();
+        let stag_hunt_proposal = |gov: &mut GovernanceState| {
+            gov.create_payoff_proposal(
+                "alice".to_string(),
+                2,
+                "Stag Hunt".to_string(),
+                stag_hunt.clone(),
+                0,
+            )
+        };
+        assert!(stag_hunt_proposal(&mut gov).is_err());
+        let id = gov
+            .create_payoff_proposal(
+                "alice".to_string(),
+                2,
+                "Raise T".to_string(),
+                PayoffMatrix { r: 2, s: -1, t: 4, p: 0 },
+                0,
+            )
+            .unwrap();
+        let proposal = gov.get_proposal(id).unwrap();
+        assert_eq!(proposal.proposal_type, ProposalType::ChangePayoff);
+        assert_eq!(proposal.payoff_matrix.as_ref().unwrap().t, 4);
+        let analysis = proposal.payoff_analysis(10).unwrap();
+        assert_eq!(analysis.class, Some(GameClass::PrisonersDilemma));
+        assert!(analysis.tft_neutrally_stable);
+
+        // Voters cannot request an unbounded analysis
+        let analysis = proposal.payoff_analysis(u32::MAX).unwrap();
+        assert_eq!(analysis.rounds, MAX_ANALYSIS_ROUNDS);
+
+        // A deployment experimenting with coordination games
+        let mut gov = GovernanceState::new_with_policy(ProposalPolicy {
+            game_policy: GamePolicy::new(vec![GameClass::PrisonersDilemma, GameClass::StagHunt]),
+            ..ProposalPolicy::default()
+        });
+        assert_eq!(gov.check_payoff_matrix(&stag_hunt), Ok(GameClass::StagHunt));
+        assert!(stag_hunt_proposal(&mut gov).is_ok());
+    }
+
+    #[test]
+    fn test_state_root_independent_of_vote_order() {
+        let mut gov_a = GovernanceState::new();
+        let mut gov_b = GovernanceState::new();
+
+        for gov in [&mut gov_a, &mut gov_b] {
+            gov.create_player_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                0,
+            )
+            .unwrap();
+        }
+
+        gov_a.vote(1, "alice".to_string(), Vote::Yes, 75, 112, 1000).unwrap();
+        gov_a.vote(1, "bob".to_string(), Vote::No, 40, 20, 1001).unwrap();
+
+        gov_b.vote(1, "bob".to_string(), Vote::No, 40, 20, 1001).unwrap();
+        gov_b.vote(1, "alice".to_string(), Vote::Yes, 75, 112, 1000).unwrap();
+
+        assert_eq!(gov_a.canonical_bytes(), gov_b.canonical_bytes());
+        assert_eq!(gov_a.state_root(), gov_b.state_root());
+    }
+
+    #[test]
+    fn test_state_root_changes_with_state() {
+        let mut gov = GovernanceState::new();
+        let empty_root = gov.state_root();
+
+        let id = gov
+            .create_player_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                0,
+            )
+            .unwrap();
+        let proposal_root = gov.state_root();
+        assert_ne!(empty_root, proposal_root);
+
+        gov.vote(id, "alice".to_string(), Vote::Yes, 75, 112, 1000).unwrap();
+        assert_ne!(proposal_root, gov.state_root());
+
+        // Same state serialized through JSON yields the same root
+        let json = serde_json::to_string(&gov).unwrap();
+        let restored: GovernanceState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.state_root(), gov.state_root());
+    }
+
+    #[test]
+    fn test_vote_inclusion_proof() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_player_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Change R to 3".to_string(),
+                0,
+            )
+            .unwrap();
+
+        for (i, voter) in ["dave", "alice", "bob"].iter().enumerate() {
+            gov.vote(id, voter.to_string(), Vote::Yes, 60, 60, 1000 + i as u64)
+                .unwrap();
+        }
+
+        let round = gov
+            .voting_rounds
+            .iter()
+            .find(|vr| vr.proposal_id == id)
+            .unwrap();
+        let root = gov.get_proposal(id).unwrap().votes_root;
+        assert_eq!(root, round.votes_root());
+        assert_ne!(root, EMPTY_ROOT);
+
+        // Bob proves his vote was counted using only his vote and the proof
+        let bob_vote = round.votes.iter().find(|v| v.address == "bob").unwrap();
+        let proof = round.inclusion_proof("bob").unwrap();
+        assert!(crate::merkle::verify_proof(&root, &vote_leaf(bob_vote), &proof));
+
+        // A vote with altered contents does not verify
+        let mut forged = bob_vote.clone();
+        forged.vote = Vote::No;
+        assert!(!crate::merkle::verify_proof(&root, &vote_leaf(&forged), &proof));
+
+        // Non-voters have no proof
+        assert!(round.inclusion_proof("mallory").is_none());
+    }
+
+    #[test]
+    fn test_ballot_inclusion_proof() {
+        let mut gov = GovernanceState::new();
+        let id = gov
+            .create_multi_option_proposal(
+                "carol".to_string(),
+                2,
+                ProposalType::ChangePayoff,
+                "Pick one".to_string(),
+                vec!["A".to_string(), "B".to_string()],
+                TallyMethod::Approval,
+                0,
+            )
+            .unwrap();
+
+        gov.vote_options(id, "alice".to_string(), vec![0], 75, 112, 1000)
+            .unwrap();
+        gov.vote_options(id, "bob".to_string(), vec![1, 0], 60, 60, 1001)
+            .unwrap();
+
+        let round = gov
+            .voting_rounds
+            .iter()
+            .find(|vr| vr.proposal_id == id)
+            .unwrap();
+        let root = gov.get_proposal(id).unwrap().votes_root;
+        let ballot = round.ballots.iter().find(|b| b.address == "alice").unwrap();
+        let proof = round.inclusion_proof("alice").unwrap();
+
+        assert!(crate::merkle::verify_proof(&root, &ballot_leaf(ballot), &proof));
+    }
 }
@@ -13,10 +13,50 @@
  */
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Bech32m taproot addresses bound to transcript keys
+pub mod address;
+
+// Game-theoretic analysis of payoff matrices
+pub mod analysis;
+
+// Canonical encoding and hashing for on-chain commitments
+pub mod encoding;
 
 // Governance module for proposal voting
 pub mod governance;
 
+// Merkle trees for vote inclusion proofs
+pub mod merkle;
+
+// Mixed strategies and 2x2 Nash equilibria
+pub mod mixed;
+
+// N-player games with many opponents per round
+pub mod multiplayer;
+
+// Seeded randomness for reproducible game mechanics
+pub mod rng;
+
+// Heuristics against reputation farming with fake opponents
+pub mod sybil;
+
+// Game transcripts signed by both players
+pub mod transcript;
+
+// Provable governance state transitions
+pub mod transition;
+
+// zkVM program input/output types
+pub mod prove;
+
+// Spell encoding and on-chain commitment
+pub mod spell;
+
+// Commit + spell Bitcoin transaction builder
+pub mod tx;
+
 /// Represents a player's action in the Prisoner's Dilemma
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Move {
@@ -39,6 +79,158 @@ pub struct RoundOutcome {
     pub payoff_2: i32,
 }
 
+/// Bit-packed move sequence: one bit per move (0 = Cooperate, 1 = Defect)
+/// Move `i` is bit `i % 8` of byte `i / 8`; unused bits of the last byte are zero.
+/// In JSON it accepts either a list of 0/1 values or `{ "len": n, "packed": "<hex>" }`,
+/// and serializes to the packed form.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "MoveHistoryRepr", into = "MoveHistoryRepr")]
+pub struct MoveHistory {
+    len: u32,
+    packed: Vec<u8>,
+}
+
+/// JSON forms of a `MoveHistory`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MoveHistoryRepr {
+    Values(Vec<u32>),
+    Packed { len: u32, packed: String },
+}
+
+impl MoveHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        MoveHistory::default()
+    }
+
+    /// Build from numeric moves (0 = Cooperate, 1 = Defect)
+    pub fn from_values(values: &[u32]) -> Result<Self, String> {
+        values
+            .iter()
+            .map(|value| match value {
+                0 => Ok(Move::Cooperate),
+                1 => Ok(Move::Defect),
+                _ => Err("Invalid move: must be 0 (Cooperate) or 1 (Defect)".to_string()),
+            })
+            .collect()
+    }
+
+    /// Build from packed bytes with an explicit move count
+    pub fn from_packed(len: u32, packed: Vec<u8>) -> Result<Self, String> {
+        if packed.len() != Self::packed_len(len) {
+            return Err(format!(
+                "Packed history of {} moves needs {} bytes, got {}",
+                len,
+                Self::packed_len(len),
+                packed.len()
+            ));
+        }
+
+        // Padding bits must be zero so each history has exactly one encoding
+        let used_bits = len % 8;
+        if used_bits != 0 && packed[packed.len() - 1] >> used_bits != 0 {
+            return Err("Packed history has non-zero padding bits".to_string());
+        }
+
+        Ok(MoveHistory { len, packed })
+    }
+
+    /// Bytes needed to pack `len` moves
+    pub fn packed_len(len: u32) -> usize {
+        (len as usize).div_ceil(8)
+    }
+
+    /// Packed bytes, without the move count
+    pub fn packed(&self) -> &[u8] {
+        &self.packed
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append a move
+    pub fn push(&mut self, move_val: Move) {
+        let bit = self.len % 8;
+        if bit == 0 {
+            self.packed.push(0);
+        }
+        if move_val == Move::Defect {
+            *self.packed.last_mut().unwrap() |= 1 << bit;
+        }
+        self.len += 1;
+    }
+
+    /// Move at `index`, if within the history
+    pub fn get(&self, index: u32) -> Option<Move> {
+        if index >= self.len {
+            return None;
+        }
+        match (self.packed[index as usize / 8] >> (index % 8)) & 1 {
+            0 => Some(Move::Cooperate),
+            _ => Some(Move::Defect),
+        }
+    }
+
+    /// Iterate moves in order
+    pub fn iter(&self) -> impl Iterator<Item = Move> + '_ {
+        (0..self.len).filter_map(move |index| self.get(index))
+    }
+
+    /// Number of defections, counted a byte at a time
+    pub fn defections(&self) -> u32 {
+        self.packed.iter().map(|byte| byte.count_ones()).sum()
+    }
+
+    /// Number of cooperative moves
+    pub fn cooperations(&self) -> u32 {
+        self.len - self.defections()
+    }
+}
+
+impl FromIterator<Move> for MoveHistory {
+    fn from_iter<I: IntoIterator<Item = Move>>(moves: I) -> Self {
+        let mut history = MoveHistory::new();
+        for move_val in moves {
+            history.push(move_val);
+        }
+        history
+    }
+}
+
+impl TryFrom<MoveHistoryRepr> for MoveHistory {
+    type Error = String;
+
+    fn try_from(repr: MoveHistoryRepr) -> Result<Self, String> {
+        match repr {
+            MoveHistoryRepr::Values(values) => MoveHistory::from_values(&values),
+            MoveHistoryRepr::Packed { len, packed } => {
+                MoveHistory::from_packed(len, encoding::from_hex(&packed)?)
+            }
+        }
+    }
+}
+
+impl From<MoveHistory> for MoveHistoryRepr {
+    fn from(history: MoveHistory) -> Self {
+        MoveHistoryRepr::Packed {
+            len: history.len,
+            packed: encoding::to_hex(&history.packed),
+        }
+    }
+}
+
+/// Most moves an opponent weighs in a reputation across opponents
+/// Below the cap an opponent counts in proportion to the moves played against them, so a
+/// swarm of one-move opponents cannot outweigh real matches; above it, one long match
+/// cannot dominate the rest.
+pub const MAX_OPPONENT_WEIGHT: u32 = 10;
+
 /// Player reputation record anchored to blockchain
 /// Calculated from game history: reputation = (cooperative_moves / total_moves) * 100
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,7 +250,8 @@ pub struct PlayerReputation {
 }
 
 impl PlayerReputation {
-    /// Calculate reputation score from game history
+    /// Calculate reputation score from one game's history
+    /// Tiers are capped by the activity minimums of `ReputationConfig::default()`.
     pub fn calculate_from_moves(
         address: String,
         total_moves: u32,
@@ -72,26 +265,103 @@ impl PlayerReputation {
             ((cooperative_moves as f64 / total_moves as f64) * 100.0).round() as u32
         };
 
+        let mut reputation =
+            PlayerReputation::from_score(address, total_moves, cooperative_moves, score);
+        reputation.cap_tier(ReputationConfig::default().max_tier(total_moves, 1));
+        reputation
+    }
+
+    /// Calculate reputation from matches against several opponents
+    /// Each `(total_moves, cooperative_moves)` pair is one opponent. The score is the mean of
+    /// the per-opponent cooperation rates, each weighted by its moves up to
+    /// `MAX_OPPONENT_WEIGHT`.
+    pub fn calculate_across_opponents(address: String, opponents: &[(u32, u32)]) -> Self {
+        PlayerReputation::calculate_with_config(
+            address,
+            opponents,
+            opponents.len() as u32,
+            &ReputationConfig::default(),
+        )
+    }
+
+    /// Calculate reputation across opponents with a custom scoring method and tier
+    /// requirements; `games` is the number of distinct games played
+    /// Each opponent's rate is adjusted for its own sample size before averaging, so many
+    /// short matches are not scored as one long one.
+    pub fn calculate_with_config(
+        address: String,
+        opponents: &[(u32, u32)],
+        games: u32,
+        config: &ReputationConfig,
+    ) -> Self {
+        let total_moves = opponents.iter().map(|(total, _)| total).sum();
+        let cooperative_moves = opponents.iter().map(|(_, cooperative)| cooperative).sum();
+
+        let rates: Vec<(f64, f64)> = opponents
+            .iter()
+            .filter(|(total, _)| *total > 0)
+            .map(|(total, cooperative)| {
+                let weight = (*total).min(MAX_OPPONENT_WEIGHT) as f64;
+                let rate = *cooperative as f64 / *total as f64;
+                (weight, config.scoring.adjust(rate, *total))
+            })
+            .collect();
+
+        // No moves against anyone yet: neutral reputation
+        let weight: f64 = rates.iter().map(|(weight, _)| weight).sum();
+        let score = if rates.is_empty() {
+            50
+        } else {
+            let rate = rates.iter().map(|(weight, rate)| weight * rate).sum::<f64>() / weight;
+            (rate * 100.0).round() as u32
+        };
+
+        let mut reputation =
+            PlayerReputation::from_score(address, total_moves, cooperative_moves, score);
+        reputation.cap_tier(config.max_tier(total_moves, games));
+        reputation
+    }
+
+    /// Derive tier and voting power for an already calculated score
+    fn from_score(address: String, total_moves: u32, cooperative_moves: u32, score: u32) -> Self {
         // Determine tier based on score
-        let (tier, voting_multiplier) = if score >= 75 {
-            (2, 1.5) // Trusted
+        let tier = if score >= 75 {
+            2 // Trusted
         } else if score >= 50 {
-            (1, 1.0) // Neutral
+            1 // Neutral
         } else {
-            (0, 0.5) // Suspicious
+            0 // Suspicious
         };
 
-        // Calculate voting power: score * tier_multiplier
-        let voting_power = (score as f64 * voting_multiplier).round() as u32;
-
         PlayerReputation {
             address,
             total_moves,
             cooperative_moves,
             reputation_score: score,
             tier,
-            voting_power,
+            voting_power: PlayerReputation::voting_power(score, tier),
+        }
+    }
+
+    /// Voting power: score * tier_multiplier
+    fn voting_power(score: u32, tier: u8) -> u32 {
+        let voting_multiplier = match tier {
+            2 => 1.5,
+            1 => 1.0,
+            _ => 0.5,
+        };
+        (score as f64 * voting_multiplier).round() as u32
+    }
+
+    /// Lower the tier to at most `max_tier`, recomputing voting power
+    /// Returns whether the tier changed.
+    pub fn cap_tier(&mut self, max_tier: u8) -> bool {
+        if self.tier <= max_tier {
+            return false;
         }
+        self.tier = max_tier;
+        self.voting_power = PlayerReputation::voting_power(self.reputation_score, max_tier);
+        true
     }
 
     /// Get reputation tier label
@@ -105,9 +375,90 @@ impl PlayerReputation {
     }
 }
 
+/// How a cooperation rate becomes a reputation score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScoringMethod {
+    /// The observed cooperation rate, however few moves it is based on
+    CooperationRate,
+    /// Posterior mean with `prior_moves` imaginary moves at a 50% cooperation rate
+    Bayesian { prior_moves: u32 },
+    /// The end of the Wilson score interval (`z` standard deviations) closest to 50%,
+    /// or 50 if the interval contains it
+    WilsonBound { z: f64 },
+}
+
+impl ScoringMethod {
+    /// Score (0-100) for a cooperation rate observed over `moves` moves (None: no moves)
+    pub fn score(&self, rate: Option<f64>, moves: u32) -> u32 {
+        match rate {
+            Some(rate) if moves > 0 => (self.adjust(rate, moves) * 100.0).round() as u32,
+            _ => 50,
+        }
+    }
+
+    /// Cooperation rate observed over `moves` (> 0) moves, adjusted for the sample size
+    pub fn adjust(&self, rate: f64, moves: u32) -> f64 {
+        let n = moves as f64;
+
+        match *self {
+            ScoringMethod::CooperationRate => rate,
+            ScoringMethod::Bayesian { prior_moves } => {
+                let prior = prior_moves as f64;
+                (rate * n + 0.5 * prior) / (n + prior)
+            }
+            ScoringMethod::WilsonBound { z } => {
+                let z2 = z * z;
+                let denominator = 1.0 + z2 / n;
+                let center = (rate + z2 / (2.0 * n)) / denominator;
+                let margin =
+                    z * (rate * (1.0 - rate) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+                if center - margin > 0.5 {
+                    center - margin
+                } else if center + margin < 0.5 {
+                    center + margin
+                } else {
+                    0.5
+                }
+            }
+        }
+    }
+}
+
+/// Scoring method and minimum activity for each reputation tier
+/// The default is the protocol's, used by the zkVM guest and `calculate_from_moves`: the
+/// plain cooperation rate, with Trusted requiring `MAX_OPPONENT_WEIGHT` moves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationConfig {
+    pub scoring: ScoringMethod,
+    /// Moves required for each tier (Suspicious, Neutral, Trusted)
+    pub min_moves: [u32; 3],
+    /// Distinct games required for each tier (Suspicious, Neutral, Trusted)
+    pub min_games: [u32; 3],
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        ReputationConfig {
+            scoring: ScoringMethod::CooperationRate,
+            min_moves: [0, 0, MAX_OPPONENT_WEIGHT],
+            min_games: [0; 3],
+        }
+    }
+}
+
+impl ReputationConfig {
+    /// Highest tier whose activity requirements are met
+    pub fn max_tier(&self, moves: u32, games: u32) -> u8 {
+        (0..3)
+            .rev()
+            .find(|tier| moves >= self.min_moves[*tier] && games >= self.min_games[*tier])
+            .unwrap_or(0) as u8
+    }
+}
+
 /// Payoff matrix for the Prisoner's Dilemma
 /// Defaults match "The Evolution of Trust"
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PayoffMatrix {
     /// Mutual cooperation reward
     pub r: i32,
@@ -130,6 +481,258 @@ impl Default for PayoffMatrix {
     }
 }
 
+/// A payoff ordering constraint that a matrix fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoffViolation {
+    /// T > R: defecting against a cooperator must tempt
+    TemptationNotAboveReward,
+    /// R > P: mutual cooperation must beat mutual defection
+    RewardNotAbovePunishment,
+    /// P > S: being exploited must be the worst outcome
+    PunishmentNotAboveSucker,
+    /// 2R > T + S: in the iterated game, alternating exploitation must not beat cooperation
+    AlternationBeatsCooperation,
+}
+
+impl fmt::Display for PayoffViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PayoffViolation::TemptationNotAboveReward => "Temptation (T) must be > Reward (R)",
+            PayoffViolation::RewardNotAbovePunishment => "Reward (R) must be > Punishment (P)",
+            PayoffViolation::PunishmentNotAboveSucker => "Punishment (P) must be > Sucker (S)",
+            PayoffViolation::AlternationBeatsCooperation => {
+                "Twice the Reward (2R) must be > Temptation + Sucker (T + S)"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+impl PayoffMatrix {
+    /// Check the Prisoner's Dilemma ordering T > R > P > S
+    pub fn validate(&self) -> Result<(), PayoffViolation> {
+        if self.t <= self.r {
+            return Err(PayoffViolation::TemptationNotAboveReward);
+        }
+        if self.r <= self.p {
+            return Err(PayoffViolation::RewardNotAbovePunishment);
+        }
+        if self.p <= self.s {
+            return Err(PayoffViolation::PunishmentNotAboveSucker);
+        }
+        Ok(())
+    }
+
+    /// Check the ordering plus the iterated-game condition 2R > T + S
+    pub fn validate_iterated(&self) -> Result<(), PayoffViolation> {
+        self.validate()?;
+        // Widened so extreme payoffs cannot overflow
+        if 2 * self.r as i64 <= self.t as i64 + self.s as i64 {
+            return Err(PayoffViolation::AlternationBeatsCooperation);
+        }
+        Ok(())
+    }
+}
+
+/// Symmetric 2x2 game family, determined by the ordering of R, T, S and P
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameClass {
+    /// T > R > P > S: defection dominates, mutual cooperation is better for both
+    PrisonersDilemma,
+    /// R > T >= P > S: cooperation pays only if the other player cooperates too
+    StagHunt,
+    /// T > R > S > P: defect against cooperators, but mutual defection is worst
+    Chicken,
+    /// T > P > R > S: defection dominates and mutual defection is preferred
+    Deadlock,
+    /// R > T, S > P: cooperation dominates, no dilemma at all
+    Harmony,
+}
+
+impl GameClass {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameClass::PrisonersDilemma => "Prisoner's Dilemma",
+            GameClass::StagHunt => "Stag Hunt",
+            GameClass::Chicken => "Chicken",
+            GameClass::Deadlock => "Deadlock",
+            GameClass::Harmony => "Harmony",
+        }
+    }
+}
+
+impl PayoffMatrix {
+    /// Classify the game this matrix defines (None for orderings outside the named families)
+    pub fn classify(&self) -> Option<GameClass> {
+        let PayoffMatrix { r, s, t, p } = *self;
+
+        if t > r && r > p && p > s {
+            Some(GameClass::PrisonersDilemma)
+        } else if r > t && t >= p && p > s {
+            Some(GameClass::StagHunt)
+        } else if t > r && r > s && s > p {
+            Some(GameClass::Chicken)
+        } else if t > p && p > r && r > s {
+            Some(GameClass::Deadlock)
+        } else if r > t && s > p {
+            Some(GameClass::Harmony)
+        } else {
+            None
+        }
+    }
+}
+
+/// Which game classes a deployment accepts for its payoff matrix
+/// The default only accepts the Prisoner's Dilemma
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GamePolicy {
+    /// Allowed game classes
+    pub allowed_classes: Vec<GameClass>,
+}
+
+impl Default for GamePolicy {
+    fn default() -> Self {
+        GamePolicy {
+            allowed_classes: vec![GameClass::PrisonersDilemma],
+        }
+    }
+}
+
+impl GamePolicy {
+    /// Create a policy allowing the given classes
+    pub fn new(allowed_classes: Vec<GameClass>) -> Self {
+        GamePolicy { allowed_classes }
+    }
+
+    pub fn allows(&self, class: GameClass) -> bool {
+        self.allowed_classes.contains(&class)
+    }
+
+    /// Check a payoff matrix against this policy, returning its class
+    pub fn check(&self, matrix: &PayoffMatrix) -> Result<GameClass, String> {
+        let class = matrix
+            .classify()
+            .ok_or("Payoff matrix does not define a recognized 2x2 game".to_string())?;
+
+        if !self.allows(class) {
+            return Err(format!("{} games are not allowed by this deployment", class.label()));
+        }
+
+        Ok(class)
+    }
+}
+
+impl encoding::CanonicalEncode for PayoffMatrix {
+    fn encode(&self, encoder: &mut encoding::Encoder) {
+        encoder.i32(self.r).i32(self.s).i32(self.t).i32(self.p);
+    }
+}
+
+impl encoding::CanonicalEncode for GameClass {
+    fn encode(&self, encoder: &mut encoding::Encoder) {
+        encoder.u8(*self as u8);
+    }
+}
+
+impl encoding::CanonicalEncode for GamePolicy {
+    fn encode(&self, encoder: &mut encoding::Encoder) {
+        // Order-independent: encode as a sorted set
+        let mut classes: Vec<u8> = self.allowed_classes.iter().map(|c| *c as u8).collect();
+        classes.sort_unstable();
+        classes.dedup();
+        encoder.u32(classes.len() as u32);
+        for class in classes {
+            encoder.u8(class);
+        }
+    }
+}
+
+impl GamePolicy {
+    /// Decode a policy written by `CanonicalEncode` (classes come back sorted)
+    pub fn decode(decoder: &mut encoding::Decoder) -> Result<Self, String> {
+        let mut allowed_classes = Vec::new();
+        for _ in 0..decoder.u32()? {
+            allowed_classes.push(match decoder.u8()? {
+                0 => GameClass::PrisonersDilemma,
+                1 => GameClass::StagHunt,
+                2 => GameClass::Chicken,
+                3 => GameClass::Deadlock,
+                4 => GameClass::Harmony,
+                other => return Err(format!("Invalid game class {}", other)),
+            });
+        }
+        Ok(GamePolicy { allowed_classes })
+    }
+}
+
+/// Separate payoff matrices for each player, for asymmetric games
+/// (e.g. miner vs. full node). Each matrix is read from its own player's perspective:
+/// `player_2.t` is what player 2 gets for defecting against a cooperating player 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AsymmetricPayoffMatrix {
+    /// Player 1's payoffs
+    pub player_1: PayoffMatrix,
+    /// Player 2's payoffs
+    pub player_2: PayoffMatrix,
+}
+
+impl Default for AsymmetricPayoffMatrix {
+    fn default() -> Self {
+        AsymmetricPayoffMatrix::symmetric(PayoffMatrix::default())
+    }
+}
+
+impl From<PayoffMatrix> for AsymmetricPayoffMatrix {
+    fn from(matrix: PayoffMatrix) -> Self {
+        AsymmetricPayoffMatrix::symmetric(matrix)
+    }
+}
+
+impl AsymmetricPayoffMatrix {
+    /// Both players share the same payoffs
+    pub fn symmetric(matrix: PayoffMatrix) -> Self {
+        AsymmetricPayoffMatrix {
+            player_1: matrix.clone(),
+            player_2: matrix,
+        }
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        self.player_1 == self.player_2
+    }
+
+    /// Check that each player faces a Prisoner's Dilemma (player 1 is checked first)
+    pub fn validate(&self) -> Result<(), PayoffViolation> {
+        self.player_1.validate()?;
+        self.player_2.validate()
+    }
+
+    /// Check that each player faces a game the policy allows (player 1 is checked first)
+    pub fn check(&self, policy: &GamePolicy) -> Result<(), String> {
+        policy.check(&self.player_1)?;
+        policy.check(&self.player_2)?;
+        Ok(())
+    }
+
+    /// Payoffs for a single round, each looked up in its player's own matrix
+    pub fn payoffs(&self, move_1: Move, move_2: Move) -> (i32, i32) {
+        let (payoff_1, _) = get_payoffs(move_1, move_2, &self.player_1);
+        let (payoff_2, _) = get_payoffs(move_2, move_1, &self.player_2);
+        (payoff_1, payoff_2)
+    }
+}
+
+/// Build from `[R, T, S, P]`, the order used by `ProveInput::payoffs`
+impl TryFrom<[i32; 4]> for PayoffMatrix {
+    type Error = PayoffViolation;
+
+    fn try_from([r, t, s, p]: [i32; 4]) -> Result<Self, PayoffViolation> {
+        let matrix = PayoffMatrix { r, s, t, p };
+        matrix.validate()?;
+        Ok(matrix)
+    }
+}
+
 /// Game state for a repeated game
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
@@ -137,8 +740,11 @@ pub struct GameState {
     pub round: u32,
     /// Total rounds in this game
     pub total_rounds: u32,
-    /// Payoff matrix
+    /// Payoff matrix (player 1's, and player 2's unless `opponent_payoff_matrix` is set)
     pub payoff_matrix: PayoffMatrix,
+    /// Player 2's payoff matrix in an asymmetric game (None when both share `payoff_matrix`)
+    #[serde(default)]
+    pub opponent_payoff_matrix: Option<PayoffMatrix>,
     /// Player 1's cumulative score
     pub score_1: i32,
     /// Player 2's cumulative score
@@ -147,6 +753,127 @@ pub struct GameState {
     pub history_1: Vec<Move>,
     /// History of player 2's moves
     pub history_2: Vec<Move>,
+    /// Random game length (None when the game lasts exactly `total_rounds`)
+    #[serde(default)]
+    pub continuation: Option<Continuation>,
+    /// Pending commit-reveal round (None when moves are submitted directly)
+    #[serde(default)]
+    pub commit_reveal: Option<CommitReveal>,
+}
+
+/// Uncertain game length: after each round, another round follows with probability `delta`
+/// Players cannot know which round is the last, so there is no final round to defect on.
+/// Each draw is seeded from a beacon revealed only after the round's moves are fixed
+/// (e.g. the hash of the next block), so nothing in the game state predicts it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Continuation {
+    /// Probability of playing another round (0.0 <= delta < 1.0)
+    pub delta: f64,
+    /// Whether a draw has ended the game
+    pub ended: bool,
+    /// Whether the last round played is waiting for its draw
+    #[serde(default)]
+    pub pending: bool,
+}
+
+impl Continuation {
+    /// Draw whether the game continues after `round` rounds, from that round's beacon
+    fn draw(&mut self, beacon: &[u8; 32], round: u32) {
+        let mut encoder = encoding::Encoder::new();
+        encoder.bytes(beacon).u32(round);
+        let mut rng = rng::SeededRng::from_commitment(&encoder.finish(), "continuation");
+        self.ended = rng.next_f64() >= self.delta;
+        self.pending = false;
+    }
+}
+
+/// One of the two players in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn index(self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+}
+
+/// Domain tag for move commitments
+const MOVE_TAG: &str = "trust-game/move/v1";
+
+/// Commitment to a move:
+/// SHA-256("trust-game/move/v1" || game_id || round || player || move || salt)
+/// with the player (0 or 1) and the move (0 or 1) as one byte each. Binding the game, round
+/// and player means a commitment cannot be replayed elsewhere or copied by the opponent.
+/// The salt must be fresh random bytes each round, or the two possible moves can be tried.
+pub fn move_commitment(
+    game_id: &[u8; 32],
+    round: u32,
+    player: Player,
+    move_val: Move,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    let mut preimage = game_id.to_vec();
+    preimage.extend_from_slice(&round.to_be_bytes());
+    preimage.push(player.index() as u8);
+    preimage.push(move_val as u8);
+    preimage.extend_from_slice(salt);
+    encoding::tagged_hash(MOVE_TAG, &preimage)
+}
+
+/// Commit-reveal state of the current round
+/// Both players commit to a move, then both reveal it. Neither can choose their move after
+/// seeing the other's. Each phase has a deadline (in caller-defined time units, e.g. block
+/// height) after which a player who has not committed or revealed is treated as defecting.
+/// The commit phase starts when the round opens (after the previous round's draw in a
+/// continuation game), so a player cannot delay it by waiting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitReveal {
+    /// Game the commitments are bound to (see `move_commitment`)
+    pub game_id: [u8; 32],
+    /// How long each phase lasts once started
+    pub timeout: u64,
+    /// Each player's commitment this round
+    pub commitments: [Option<[u8; 32]>; 2],
+    /// Each player's revealed move this round (Defect for a missed deadline)
+    pub reveals: [Option<Move>; 2],
+    /// Whether the commit phase is over
+    pub revealing: bool,
+    /// Deadline of the current phase
+    pub deadline: u64,
+}
+
+impl CommitReveal {
+    /// Commit-reveal state for a game whose first round opens at `now`
+    pub fn new(game_id: [u8; 32], timeout: u64, now: u64) -> Self {
+        CommitReveal {
+            game_id,
+            timeout,
+            commitments: [None; 2],
+            reveals: [None; 2],
+            revealing: false,
+            deadline: now.saturating_add(timeout),
+        }
+    }
+
+    fn expired(&self, now: u64) -> bool {
+        now > self.deadline
+    }
+
+    fn start_reveal_phase(&mut self, now: u64) {
+        self.revealing = true;
+        self.deadline = now.saturating_add(self.timeout);
+    }
+
+    /// Clear the round's commitments and start the next round's commit phase
+    fn open_round(&mut self, now: u64) {
+        *self = CommitReveal::new(self.game_id, self.timeout, now);
+    }
 }
 
 impl GameState {
@@ -156,23 +883,77 @@ impl GameState {
             round: 0,
             total_rounds,
             payoff_matrix: PayoffMatrix::default(),
+            opponent_payoff_matrix: None,
             score_1: 0,
             score_2: 0,
             history_1: Vec::new(),
             history_2: Vec::new(),
+            continuation: None,
+            commit_reveal: None,
+        }
+    }
+
+    /// Create a game that ends after each round with probability `1 - delta`
+    /// Every round is followed by a draw (see `RoundValidator::draw_continuation`).
+    /// `total_rounds` is kept as a hard cap at `u32::MAX`.
+    pub fn new_with_continuation(delta: f64) -> Result<Self, String> {
+        if !(0.0..1.0).contains(&delta) {
+            return Err(format!("Continuation probability must be in [0, 1), got {}", delta));
         }
+
+        Ok(GameState {
+            continuation: Some(Continuation {
+                delta,
+                ended: false,
+                pending: false,
+            }),
+            ..GameState::new(u32::MAX)
+        })
+    }
+
+    /// Payoff matrices of both players
+    pub fn payoff_matrices(&self) -> AsymmetricPayoffMatrix {
+        AsymmetricPayoffMatrix {
+            player_1: self.payoff_matrix.clone(),
+            player_2: self
+                .opponent_payoff_matrix
+                .clone()
+                .unwrap_or_else(|| self.payoff_matrix.clone()),
+        }
+    }
+
+    /// Check if game is finished (round cap reached, or ended by a continuation draw)
+    pub fn is_finished(&self) -> bool {
+        let ended = self.continuation.as_ref().is_some_and(|c| c.ended);
+        ended || self.round >= self.total_rounds
     }
 
     /// Create a new game with custom payoff matrix
     pub fn new_with_payoffs(total_rounds: u32, payoff_matrix: PayoffMatrix) -> Self {
+        GameState::new_with_asymmetric_payoffs(total_rounds, payoff_matrix.into())
+    }
+
+    /// Create a new game where each player has their own payoffs
+    pub fn new_with_asymmetric_payoffs(
+        total_rounds: u32,
+        payoff_matrix: AsymmetricPayoffMatrix,
+    ) -> Self {
+        let opponent_payoff_matrix = if payoff_matrix.is_symmetric() {
+            None
+        } else {
+            Some(payoff_matrix.player_2)
+        };
         GameState {
             round: 0,
             total_rounds,
-            payoff_matrix,
+            payoff_matrix: payoff_matrix.player_1,
+            opponent_payoff_matrix,
             score_1: 0,
             score_2: 0,
             history_1: Vec::new(),
             history_2: Vec::new(),
+            continuation: None,
+            commit_reveal: None,
         }
     }
 }
@@ -197,6 +978,9 @@ pub fn get_payoffs(move_1: Move, move_2: Move, payoff_matrix: &PayoffMatrix) ->
 /// 1. Move must be valid (Cooperate or Defect)
 /// 2. Payoff calculation must be correct
 /// 3. Game state must be consistent
+/// 4. Mutual cooperation must pay more than mutual defection (R > P) for both players
+///
+/// The game class is not checked; see `validate_move_with_policy`.
 pub fn validate_move(
     state: &GameState,
     move_1: Move,
@@ -204,36 +988,57 @@ pub fn validate_move(
     claimed_payoff_1: i32,
     claimed_payoff_2: i32,
 ) -> bool {
-    // Verify round is within bounds
-    if state.round >= state.total_rounds {
-        return false;
-    }
-
-    // Calculate actual payoffs
-    let (actual_payoff_1, actual_payoff_2) =
-        get_payoffs(move_1, move_2, &state.payoff_matrix);
-
-    // Verify payoffs match
-    if claimed_payoff_1 != actual_payoff_1 || claimed_payoff_2 != actual_payoff_2 {
+    if !payoffs_match(state, move_1, move_2, claimed_payoff_1, claimed_payoff_2) {
         return false;
     }
 
     // Verify payoff matrix is reasonable (for Bitcoin context)
     // Mutual cooperation should be better than mutual defection
-    if state.payoff_matrix.r <= state.payoff_matrix.p {
-        return false;
-    }
-
-    true
+    let payoffs = state.payoff_matrices();
+    payoffs.player_1.r > payoffs.player_1.p && payoffs.player_2.r > payoffs.player_2.p
 }
 
-/// Validate strategy consistency for Tit-for-Tat
-/// (copies opponent's previous move)
-pub fn validate_tft_strategy(
+/// Validate a move in a deployment that restricts which games may be played
+/// Each player's payoff matrix must be a game class `policy` allows.
+pub fn validate_move_with_policy(
     state: &GameState,
-    proposed_move: Move,
+    policy: &GamePolicy,
+    move_1: Move,
+    move_2: Move,
+    claimed_payoff_1: i32,
+    claimed_payoff_2: i32,
 ) -> bool {
-    if state.round == 0 {
+    payoffs_match(state, move_1, move_2, claimed_payoff_1, claimed_payoff_2)
+        && state.payoff_matrices().check(policy).is_ok()
+}
+
+/// Whether the game is still running and the claimed payoffs are the round's payoffs
+fn payoffs_match(
+    state: &GameState,
+    move_1: Move,
+    move_2: Move,
+    claimed_payoff_1: i32,
+    claimed_payoff_2: i32,
+) -> bool {
+    // Verify round is within bounds
+    if state.is_finished() {
+        return false;
+    }
+
+    // Calculate actual payoffs
+    let (actual_payoff_1, actual_payoff_2) = state.payoff_matrices().payoffs(move_1, move_2);
+
+    // Verify payoffs match
+    claimed_payoff_1 == actual_payoff_1 && claimed_payoff_2 == actual_payoff_2
+}
+
+/// Validate strategy consistency for Tit-for-Tat
+/// (copies opponent's previous move)
+pub fn validate_tft_strategy(
+    state: &GameState,
+    proposed_move: Move,
+) -> bool {
+    if state.round == 0 {
         // First round should be cooperate
         return proposed_move == Move::Cooperate;
     }
@@ -274,6 +1079,68 @@ pub fn validate_grudge_strategy(
     proposed_move == Move::Cooperate
 }
 
+/// Built-in strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Strategy {
+    AlwaysCooperate,
+    AlwaysDefect,
+    /// Cooperate first, then copy the opponent's previous move
+    TitForTat,
+    /// Cooperate until the opponent defects once, then always defect
+    Grudge,
+    /// Cooperate or defect with equal probability
+    Random,
+}
+
+impl Strategy {
+    /// Every built-in strategy
+    pub const ALL: [Strategy; 5] = [
+        Strategy::AlwaysCooperate,
+        Strategy::AlwaysDefect,
+        Strategy::TitForTat,
+        Strategy::Grudge,
+        Strategy::Random,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::AlwaysCooperate => "Always Cooperate",
+            Strategy::AlwaysDefect => "Always Defect",
+            Strategy::TitForTat => "Tit-for-Tat",
+            Strategy::Grudge => "Grudge",
+            Strategy::Random => "Random",
+        }
+    }
+
+    /// Next move distribution, given the opponent's previous move (None in the first
+    /// round) and whether the opponent has ever defected
+    pub fn respond(
+        &self,
+        opponent_last: Option<Move>,
+        opponent_ever_defected: bool,
+    ) -> mixed::MixedStrategy {
+        let cooperate_if = |cooperate: bool| {
+            mixed::MixedStrategy::pure(if cooperate { Move::Cooperate } else { Move::Defect })
+        };
+
+        match self {
+            Strategy::AlwaysCooperate => cooperate_if(true),
+            Strategy::AlwaysDefect => cooperate_if(false),
+            Strategy::TitForTat => cooperate_if(opponent_last != Some(Move::Defect)),
+            Strategy::Grudge => cooperate_if(!opponent_ever_defected),
+            Strategy::Random => mixed::MixedStrategy::uniform(),
+        }
+    }
+
+    /// Next move distribution given the opponent's full history
+    pub fn next_move(&self, opponent_history: &[Move]) -> mixed::MixedStrategy {
+        self.respond(
+            opponent_history.last().copied(),
+            opponent_history.contains(&Move::Defect),
+        )
+    }
+}
+
 /// Validate a complete game round
 pub struct RoundValidator {
     pub state: GameState,
@@ -284,19 +1151,174 @@ impl RoundValidator {
         RoundValidator { state }
     }
 
+    /// Validator for a game whose moves must be committed and revealed
+    /// `game_id` identifies this game (e.g. `GameTranscript::game_id`). Each commit or reveal
+    /// phase lasts `timeout` time units once started; the first round opens at `now`.
+    pub fn new_with_commit_reveal(
+        mut state: GameState,
+        game_id: [u8; 32],
+        timeout: u64,
+        now: u64,
+    ) -> Self {
+        state.commit_reveal = Some(CommitReveal::new(game_id, timeout, now));
+        RoundValidator { state }
+    }
+
     /// Execute and validate a round
     pub fn play_round(
         &mut self,
         move_1: Move,
         move_2: Move,
     ) -> Result<RoundOutcome, String> {
+        if self.state.commit_reveal.is_some() {
+            return Err("Moves must be committed and revealed".to_string());
+        }
+        self.check_draw_done()?;
+        self.apply_round(move_1, move_2)
+    }
+
+    /// Decide whether a continuation game goes on after the round just played
+    /// `beacon` must be revealed only after both of that round's moves were fixed, e.g. the
+    /// hash of the first block after the moves were committed. Returns whether another
+    /// round follows; with commit-reveal, its commit phase opens at `now`.
+    pub fn draw_continuation(&mut self, beacon: &[u8; 32], now: u64) -> Result<bool, String> {
+        let round = self.state.round;
+        let continuation = self
+            .state
+            .continuation
+            .as_mut()
+            .ok_or("Game has a fixed length".to_string())?;
+        if !continuation.pending {
+            return Err("No continuation draw is pending".to_string());
+        }
+
+        continuation.draw(beacon, round);
+        let continues = !continuation.ended;
+
+        if let Some(protocol) = &mut self.state.commit_reveal {
+            protocol.open_round(now);
+        }
+        Ok(continues)
+    }
+
+    /// Rounds of a continuation game cannot start before the previous round's draw
+    fn check_draw_done(&self) -> Result<(), String> {
+        match &self.state.continuation {
+            Some(continuation) if continuation.pending => Err(format!(
+                "Continuation draw for round {} is pending",
+                self.state.round
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Commit to this round's move (see `move_commitment`)
+    pub fn commit(&mut self, player: Player, commitment: [u8; 32], now: u64) -> Result<(), String> {
+        if self.state.is_finished() {
+            return Err("Game already finished".to_string());
+        }
+        self.check_draw_done()?;
+        let protocol = self.commit_reveal_mut()?;
+
+        if protocol.revealing || protocol.expired(now) {
+            return Err("Commit phase is over".to_string());
+        }
+        if protocol.commitments[player.index()].is_some() {
+            return Err(format!("{:?} already committed this round", player));
+        }
+        if protocol.commitments.contains(&Some(commitment)) {
+            return Err("Commitment copies the other player's".to_string());
+        }
+
+        protocol.commitments[player.index()] = Some(commitment);
+        if protocol.commitments.iter().all(Option::is_some) {
+            protocol.start_reveal_phase(now);
+        }
+        Ok(())
+    }
+
+    /// Reveal a committed move; plays the round once both moves are known
+    pub fn reveal(
+        &mut self,
+        player: Player,
+        move_val: Move,
+        salt: &[u8; 32],
+        now: u64,
+    ) -> Result<Option<RoundOutcome>, String> {
+        self.check_draw_done()?;
+        let round = self.state.round;
+        let protocol = self.commit_reveal_mut()?;
+
+        if !protocol.revealing {
+            return Err("Reveal phase has not started".to_string());
+        }
+        if protocol.expired(now) {
+            return Err("Reveal phase is over".to_string());
+        }
+        if protocol.reveals[player.index()].is_some() {
+            return Err(format!("{:?} already revealed this round", player));
+        }
+        let commitment = move_commitment(&protocol.game_id, round, player, move_val, salt);
+        if protocol.commitments[player.index()] != Some(commitment) {
+            return Err("Revealed move does not match commitment".to_string());
+        }
+
+        protocol.reveals[player.index()] = Some(move_val);
+        self.complete_round(now)
+    }
+
+    /// Apply the current phase's deadline: players who missed it are treated as defecting
+    /// A missed commit starts the reveal phase for whoever did commit; a missed reveal
+    /// plays the round.
+    pub fn resolve_timeout(&mut self, now: u64) -> Result<Option<RoundOutcome>, String> {
+        self.check_draw_done()?;
+        let protocol = self.commit_reveal_mut()?;
+        if !protocol.expired(now) {
+            return Ok(None);
+        }
+
+        for (commitment, reveal) in protocol.commitments.iter().zip(protocol.reveals.iter_mut()) {
+            if commitment.is_none() || protocol.revealing {
+                reveal.get_or_insert(Move::Defect);
+            }
+        }
+        if !protocol.revealing {
+            protocol.start_reveal_phase(now);
+        }
+        self.complete_round(now)
+    }
+
+    fn commit_reveal_mut(&mut self) -> Result<&mut CommitReveal, String> {
+        self.state
+            .commit_reveal
+            .as_mut()
+            .ok_or_else(|| "Game does not use commit-reveal".to_string())
+    }
+
+    /// Play the round if both moves are known, opening the next round at `now`
+    /// (continuation games open it at the draw instead)
+    fn complete_round(&mut self, now: u64) -> Result<Option<RoundOutcome>, String> {
+        let continuation = self.state.continuation.is_some();
+        let protocol = self.commit_reveal_mut()?;
+        let (move_1, move_2) = match protocol.reveals {
+            [Some(move_1), Some(move_2)] => (move_1, move_2),
+            _ => return Ok(None),
+        };
+
+        if !continuation {
+            protocol.open_round(now);
+        }
+        self.apply_round(move_1, move_2).map(Some)
+    }
+
+    fn apply_round(&mut self, move_1: Move, move_2: Move) -> Result<RoundOutcome, String> {
         // Check round bounds
-        if self.state.round >= self.state.total_rounds {
+        if self.state.is_finished() {
             return Err("Game already finished".to_string());
         }
 
         // Calculate payoffs
-        let (payoff_1, payoff_2) = get_payoffs(move_1, move_2, &self.state.payoff_matrix);
+        let (payoff_1, payoff_2) = self.state.payoff_matrices().payoffs(move_1, move_2);
 
         // Update state
         self.state.history_1.push(move_1);
@@ -305,6 +1327,11 @@ impl RoundValidator {
         self.state.score_2 += payoff_2;
         self.state.round += 1;
 
+        // Whether another round follows is drawn once the round's beacon is known
+        if let Some(continuation) = &mut self.state.continuation {
+            continuation.pending = true;
+        }
+
         Ok(RoundOutcome {
             move_1,
             move_2,
@@ -320,7 +1347,7 @@ impl RoundValidator {
 
     /// Check if game is finished
     pub fn is_finished(&self) -> bool {
-        self.state.round >= self.state.total_rounds
+        self.state.is_finished()
     }
 }
 
@@ -371,6 +1398,178 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_payoff_matrix_validation() {
+        assert_eq!(PayoffMatrix::default().validate(), Ok(()));
+        assert_eq!(PayoffMatrix::default().validate_iterated(), Ok(()));
+
+        // [R, T, S, P] order, as in ProveInput
+        let matrix = PayoffMatrix::try_from([3, 5, 0, 1]).unwrap();
+        assert_eq!((matrix.r, matrix.t, matrix.s, matrix.p), (3, 5, 0, 1));
+
+        assert_eq!(
+            PayoffMatrix::try_from([3, 3, -1, 0]).unwrap_err(),
+            PayoffViolation::TemptationNotAboveReward
+        );
+        assert_eq!(
+            PayoffMatrix::try_from([2, 3, -1, 2]).unwrap_err(),
+            PayoffViolation::RewardNotAbovePunishment
+        );
+        assert_eq!(
+            PayoffMatrix::try_from([2, 3, 0, 0]).unwrap_err(),
+            PayoffViolation::PunishmentNotAboveSucker
+        );
+
+        // A one-shot PD where taking turns exploiting each other beats cooperating
+        let alternating = PayoffMatrix::try_from([2, 5, 0, 1]).unwrap();
+        assert_eq!(
+            alternating.validate_iterated(),
+            Err(PayoffViolation::AlternationBeatsCooperation)
+        );
+        assert_eq!(
+            PayoffViolation::RewardNotAbovePunishment.to_string(),
+            "Reward (R) must be > Punishment (P)"
+        );
+    }
+
+    #[test]
+    fn test_asymmetric_payoffs() {
+        // Miner (player 1) gains more from defecting than the full node (player 2)
+        let payoffs = AsymmetricPayoffMatrix {
+            player_1: PayoffMatrix { r: 2, s: -1, t: 5, p: 0 },
+            player_2: PayoffMatrix { r: 3, s: -4, t: 4, p: -1 },
+        };
+        assert!(payoffs.validate().is_ok());
+        assert!(!payoffs.is_symmetric());
+
+        assert_eq!(payoffs.payoffs(Move::Cooperate, Move::Cooperate), (2, 3));
+        assert_eq!(payoffs.payoffs(Move::Defect, Move::Cooperate), (5, -4));
+        assert_eq!(payoffs.payoffs(Move::Cooperate, Move::Defect), (-1, 4));
+        assert_eq!(payoffs.payoffs(Move::Defect, Move::Defect), (0, -1));
+
+        let mut validator =
+            RoundValidator::new(GameState::new_with_asymmetric_payoffs(2, payoffs.clone()));
+        validator.play_round(Move::Defect, Move::Cooperate).unwrap();
+        validator.play_round(Move::Cooperate, Move::Cooperate).unwrap();
+        assert_eq!((validator.state.score_1, validator.state.score_2), (7, -1));
+
+        // Only an asymmetric game stores a second matrix, so existing state JSON still loads
+        assert_eq!(validator.state.opponent_payoff_matrix, Some(payoffs.player_2.clone()));
+        assert_eq!(validator.state.payoff_matrices(), payoffs);
+        let json = r#"{"round": 0, "total_rounds": 2, "payoff_matrix": {"r": 2, "s": -1, "t": 3,
+            "p": 0}, "score_1": 0, "score_2": 0, "history_1": [], "history_2": []}"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.opponent_payoff_matrix, None);
+        assert!(state.payoff_matrices().is_symmetric());
+
+        // One player's matrix failing the PD ordering invalidates the game
+        let invalid = AsymmetricPayoffMatrix {
+            player_2: PayoffMatrix { r: 3, s: -4, t: 2, p: -1 },
+            ..payoffs
+        };
+        assert_eq!(invalid.validate(), Err(PayoffViolation::TemptationNotAboveReward));
+        let state = GameState::new_with_asymmetric_payoffs(10, invalid);
+        let policy = GamePolicy::default();
+        assert!(!validate_move_with_policy(
+            &state,
+            &policy,
+            Move::Cooperate,
+            Move::Cooperate,
+            2,
+            3
+        ));
+
+        // Without a policy only R > P is required, of both players
+        assert!(validate_move(&state, Move::Cooperate, Move::Cooperate, 2, 3));
+        let no_reward = AsymmetricPayoffMatrix {
+            player_2: PayoffMatrix { r: -1, s: -4, t: 2, p: -1 },
+            ..state.payoff_matrices()
+        };
+        let state = GameState::new_with_asymmetric_payoffs(10, no_reward);
+        assert!(!validate_move(&state, Move::Cooperate, Move::Cooperate, 2, -1));
+    }
+
+    #[test]
+    fn test_symmetric_payoffs_match_get_payoffs() {
+        let matrix = PayoffMatrix::default();
+        let payoffs = AsymmetricPayoffMatrix::from(matrix.clone());
+        assert!(payoffs.is_symmetric());
+
+        for move_1 in [Move::Cooperate, Move::Defect] {
+            for move_2 in [Move::Cooperate, Move::Defect] {
+                assert_eq!(payoffs.payoffs(move_1, move_2), get_payoffs(move_1, move_2, &matrix));
+            }
+        }
+    }
+
+    #[test]
+    fn test_game_classification() {
+        let classify = |r, t, s, p| PayoffMatrix { r, s, t, p }.classify();
+
+        assert_eq!(classify(2, 3, -1, 0), Some(GameClass::PrisonersDilemma));
+        assert_eq!(classify(4, 3, -1, 0), Some(GameClass::StagHunt));
+        assert_eq!(classify(2, 3, 0, -1), Some(GameClass::Chicken));
+        assert_eq!(classify(1, 3, -1, 2), Some(GameClass::Deadlock));
+        assert_eq!(classify(4, 3, 1, 0), Some(GameClass::Harmony));
+        assert_eq!(classify(1, 1, 1, 1), None);
+    }
+
+    #[test]
+    fn test_game_policy() {
+        let stag_hunt = PayoffMatrix { r: 4, s: -1, t: 3, p: 0 };
+
+        // Default deployments only play the Prisoner's Dilemma
+        let policy = GamePolicy::default();
+        assert_eq!(policy.check(&PayoffMatrix::default()), Ok(GameClass::PrisonersDilemma));
+        assert_eq!(
+            policy.check(&stag_hunt).unwrap_err(),
+            "Stag Hunt games are not allowed by this deployment"
+        );
+
+        let policy = GamePolicy::new(vec![GameClass::PrisonersDilemma, GameClass::StagHunt]);
+        assert_eq!(policy.check(&stag_hunt), Ok(GameClass::StagHunt));
+        assert!(policy.check(&PayoffMatrix { r: 1, s: 1, t: 1, p: 1 }).is_err());
+    }
+
+    #[test]
+    fn test_validate_move_checks_game_policy() {
+        // R > P holds, but T < R makes this a Stag Hunt
+        let matrix = PayoffMatrix { r: 3, s: -1, t: 2, p: 0 };
+        let state = GameState::new_with_payoffs(10, matrix);
+
+        // Accepted without a policy, as R > P
+        assert!(validate_move(&state, Move::Cooperate, Move::Cooperate, 3, 3));
+
+        // Rejected by the default (Prisoner's Dilemma only) policy
+        let default_policy = GamePolicy::default();
+        assert!(!validate_move_with_policy(
+            &state,
+            &default_policy,
+            Move::Cooperate,
+            Move::Cooperate,
+            3,
+            3
+        ));
+
+        // Accepted where the deployment allows Stag Hunt games
+        let policy = GamePolicy::new(vec![GameClass::PrisonersDilemma, GameClass::StagHunt]);
+        assert!(validate_move_with_policy(&state, &policy, Move::Cooperate, Move::Cooperate, 3, 3));
+        assert!(!validate_move_with_policy(
+            &state,
+            &policy,
+            Move::Cooperate,
+            Move::Cooperate,
+            2,
+            2
+        ));
+        assert_eq!(state.payoff_matrices().check(&policy), Ok(()));
+
+        // Policies survive encoding
+        let bytes = encoding::CanonicalEncode::canonical_bytes(&policy);
+        let mut decoder = encoding::Decoder::new(&bytes);
+        assert_eq!(GamePolicy::decode(&mut decoder).unwrap(), policy);
+    }
+
     #[test]
     fn test_tft_strategy() {
         let mut state = GameState::new(5);
@@ -448,6 +1647,359 @@ mod tests {
         assert_eq!(rep.get_tier_label(), "Suspicious");
     }
 
+    #[test]
+    fn test_strategy_matches_validators() {
+        let mut validator = RoundValidator::new(GameState::new(4));
+        for opponent_move in [Move::Cooperate, Move::Defect, Move::Cooperate] {
+            let state = validator.get_state();
+            let history = &state.history_2;
+
+            let tft = Strategy::TitForTat.next_move(history).as_pure().unwrap();
+            assert!(validate_tft_strategy(state, tft));
+            let grudge = Strategy::Grudge.next_move(history).as_pure().unwrap();
+            assert!(validate_grudge_strategy(state, grudge));
+
+            validator.play_round(tft, opponent_move).unwrap();
+        }
+
+        assert_eq!(Strategy::Random.next_move(&[]).as_pure(), None);
+    }
+
+    /// Beacon revealed after a round's moves (stands in for a block hash)
+    fn beacon(seed: u64, round: u32) -> [u8; 32] {
+        let mut encoder = encoding::Encoder::new();
+        encoder.u64(seed).u32(round);
+        encoding::sha256(&encoder.finish())
+    }
+
+    /// Rounds played before a continuation game ends
+    fn continuation_length(delta: f64, seed: u64) -> u32 {
+        let state = GameState::new_with_continuation(delta).unwrap();
+        let mut validator = RoundValidator::new(state);
+        while !validator.is_finished() {
+            validator.play_round(Move::Cooperate, Move::Cooperate).unwrap();
+            let continues = validator.draw_continuation(&beacon(seed, validator.state.round), 0);
+            assert_eq!(continues.unwrap(), !validator.is_finished());
+        }
+        validator.state.round
+    }
+
+    #[test]
+    fn test_continuation_game_length() {
+        // delta = 0: the game always ends after one round
+        assert_eq!(continuation_length(0.0, 7), 1);
+
+        // The beacons fix the length
+        assert_eq!(continuation_length(0.9, 7), continuation_length(0.9, 7));
+
+        // Expected length is 1 / (1 - delta)
+        let games = 2_000;
+        let total: u32 = (0..games).map(|seed| continuation_length(0.9, seed)).sum();
+        let mean = total as f64 / games as f64;
+        assert!((mean - 10.0).abs() < 1.0, "mean length {}", mean);
+
+        assert!(GameState::new_with_continuation(1.0).is_err());
+    }
+
+    #[test]
+    fn test_continuation_draw_follows_moves() {
+        let state = GameState::new_with_continuation(0.9).unwrap();
+        let mut validator = RoundValidator::new(state);
+        assert_eq!(
+            validator.draw_continuation(&beacon(1, 0), 0),
+            Err("No continuation draw is pending".to_string())
+        );
+
+        // The next round waits for the draw, and the state holds nothing to predict it with
+        validator.play_round(Move::Cooperate, Move::Cooperate).unwrap();
+        assert_eq!(
+            validator.play_round(Move::Cooperate, Move::Cooperate).unwrap_err(),
+            "Continuation draw for round 1 is pending"
+        );
+        let json = serde_json::to_value(validator.get_state()).unwrap();
+        assert_eq!(
+            json["continuation"],
+            serde_json::json!({"delta": 0.9, "ended": false, "pending": true})
+        );
+
+        // Fixed-length games have no draws
+        let mut fixed = RoundValidator::new(GameState::new(2));
+        fixed.play_round(Move::Cooperate, Move::Cooperate).unwrap();
+        assert_eq!(
+            fixed.draw_continuation(&beacon(1, 1), 0),
+            Err("Game has a fixed length".to_string())
+        );
+    }
+
+    #[test]
+    fn test_continuation_game_rejects_moves_after_end() {
+        let state = GameState::new_with_continuation(0.0).unwrap();
+        let mut validator = RoundValidator::new(state);
+        validator.play_round(Move::Cooperate, Move::Cooperate).unwrap();
+        assert_eq!(validator.draw_continuation(&beacon(1, 1), 0), Ok(false));
+
+        assert!(validator.is_finished());
+        assert!(validator.play_round(Move::Cooperate, Move::Cooperate).is_err());
+        assert!(!validate_move(validator.get_state(), Move::Cooperate, Move::Cooperate, 2, 2));
+    }
+
+    #[test]
+    fn test_continuation_timeout_waits_for_draw() {
+        let state = GameState::new_with_continuation(0.0).unwrap();
+        let mut validator = RoundValidator::new_with_commit_reveal(state, GAME_ID, 10, 0);
+        let salt = [7u8; 32];
+        for player in [Player::One, Player::Two] {
+            let commitment = commitment(0, player, Move::Cooperate, &salt);
+            validator.commit(player, commitment, 0).unwrap();
+        }
+        validator.reveal(Player::One, Move::Cooperate, &salt, 1).unwrap();
+        validator.reveal(Player::Two, Move::Cooperate, &salt, 1).unwrap().unwrap();
+
+        // A missed deadline cannot play a round whose draw is still pending
+        assert_eq!(
+            validator.resolve_timeout(100).unwrap_err(),
+            "Continuation draw for round 1 is pending"
+        );
+        assert_eq!(validator.get_state().round, 1);
+
+        // Once the draw ends the game, timeouts cannot add rounds either
+        assert_eq!(validator.draw_continuation(&beacon(1, 1), 100), Ok(false));
+        assert!(validator.resolve_timeout(200).is_err());
+        assert_eq!(validator.get_state().round, 1);
+    }
+
+    /// Game the commit-reveal tests commit to
+    const GAME_ID: [u8; 32] = [9u8; 32];
+
+    /// Commit-reveal validator for a 3-round game opening at 0, with 10-unit phases
+    fn commit_reveal_game() -> RoundValidator {
+        RoundValidator::new_with_commit_reveal(GameState::new(3), GAME_ID, 10, 0)
+    }
+
+    /// Commitment to a move in `round` of the test game
+    fn commitment(round: u32, player: Player, move_val: Move, salt: &[u8; 32]) -> [u8; 32] {
+        move_commitment(&GAME_ID, round, player, move_val, salt)
+    }
+
+    #[test]
+    fn test_move_commitment_binding() {
+        let salt = [7u8; 32];
+        let base = commitment(0, Player::One, Move::Cooperate, &salt);
+
+        // Domain-separated SHA-256 over game id, round, player, move and salt
+        let mut preimage = GAME_ID.to_vec();
+        preimage.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        preimage.extend_from_slice(&salt);
+        assert_eq!(base, encoding::tagged_hash("trust-game/move/v1", &preimage));
+
+        // Every field changes the commitment
+        assert_ne!(base, move_commitment(&[8u8; 32], 0, Player::One, Move::Cooperate, &salt));
+        assert_ne!(base, commitment(1, Player::One, Move::Cooperate, &salt));
+        assert_ne!(base, commitment(0, Player::Two, Move::Cooperate, &salt));
+        assert_ne!(base, commitment(0, Player::One, Move::Defect, &salt));
+    }
+
+    #[test]
+    fn test_commit_reveal_round() {
+        let mut validator = commit_reveal_game();
+        let (salt_1, salt_2) = ([1u8; 32], [2u8; 32]);
+
+        // Moves cannot bypass the protocol
+        assert!(validator.play_round(Move::Cooperate, Move::Cooperate).is_err());
+        assert!(validator.reveal(Player::One, Move::Cooperate, &salt_1, 0).is_err());
+
+        let commitment_1 = commitment(0, Player::One, Move::Cooperate, &salt_1);
+        validator.commit(Player::One, commitment_1, 0).unwrap();
+        assert!(validator.commit(Player::One, commitment_1, 1).is_err());
+
+        // Player 2 cannot copy player 1's commitment to mirror their move
+        assert_eq!(
+            validator.commit(Player::Two, commitment_1, 1),
+            Err("Commitment copies the other player's".to_string())
+        );
+        validator
+            .commit(Player::Two, commitment(0, Player::Two, Move::Defect, &salt_2), 2)
+            .unwrap();
+
+        // A reveal must match the commitment, so player 2 cannot switch to cooperating
+        assert!(validator.reveal(Player::Two, Move::Cooperate, &salt_2, 3).is_err());
+        assert!(validator.reveal(Player::One, Move::Cooperate, &salt_1, 3).unwrap().is_none());
+        let outcome = validator.reveal(Player::Two, Move::Defect, &salt_2, 4).unwrap().unwrap();
+
+        assert_eq!((outcome.move_1, outcome.move_2), (Move::Cooperate, Move::Defect));
+        assert_eq!((outcome.payoff_1, outcome.payoff_2), (-1, 3));
+        assert_eq!(validator.get_state().round, 1);
+        let protocol = validator.get_state().commit_reveal.as_ref().unwrap();
+        assert!(protocol.commitments[0].is_none());
+        assert_eq!(protocol.deadline, 14);
+
+        // A commitment replayed from the last round cannot be revealed in this one
+        validator.commit(Player::One, commitment_1, 5).unwrap();
+        let commitment_2 = commitment(1, Player::Two, Move::Defect, &salt_2);
+        validator.commit(Player::Two, commitment_2, 5).unwrap();
+        assert_eq!(
+            validator.reveal(Player::One, Move::Cooperate, &salt_1, 6).unwrap_err(),
+            "Revealed move does not match commitment"
+        );
+    }
+
+    #[test]
+    fn test_commit_reveal_timeouts_count_as_defection() {
+        let mut validator = commit_reveal_game();
+        let salt = [7u8; 32];
+
+        // Player 2 never reveals
+        let commitment_1 = commitment(0, Player::One, Move::Cooperate, &salt);
+        validator.commit(Player::One, commitment_1, 0).unwrap();
+        let commitment_2 = commitment(0, Player::Two, Move::Cooperate, &salt);
+        validator.commit(Player::Two, commitment_2, 5).unwrap();
+        validator.reveal(Player::One, Move::Cooperate, &salt, 6).unwrap();
+        assert!(validator.resolve_timeout(15).unwrap().is_none());
+        assert!(validator.reveal(Player::Two, Move::Cooperate, &salt, 16).is_err());
+        let outcome = validator.resolve_timeout(16).unwrap().unwrap();
+        assert_eq!((outcome.move_1, outcome.move_2), (Move::Cooperate, Move::Defect));
+
+        // Player 1 never commits: the commit phase opened with the round at 16, not with
+        // player 2's commitment, so it closes at 26
+        let commitment_2 = commitment(1, Player::Two, Move::Cooperate, &salt);
+        validator.commit(Player::Two, commitment_2, 20).unwrap();
+        assert!(validator.resolve_timeout(26).unwrap().is_none());
+        assert!(!validator.get_state().commit_reveal.as_ref().unwrap().revealing);
+        assert!(validator.resolve_timeout(27).unwrap().is_none());
+        assert!(validator.commit(Player::One, [0u8; 32], 28).is_err());
+
+        // Player 2 still has to reveal
+        let outcome = validator.reveal(Player::Two, Move::Cooperate, &salt, 30).unwrap().unwrap();
+        assert_eq!((outcome.move_1, outcome.move_2), (Move::Defect, Move::Cooperate));
+
+        assert_eq!(validator.get_state().history_1, vec![Move::Cooperate, Move::Defect]);
+    }
+
+    #[test]
+    fn test_commit_reveal_continuation_game() {
+        let state = GameState::new_with_continuation(0.9).unwrap();
+        let mut validator = RoundValidator::new_with_commit_reveal(state, GAME_ID, 10, 0);
+        let salt = [7u8; 32];
+
+        for player in [Player::One, Player::Two] {
+            let commitment = commitment(0, player, Move::Cooperate, &salt);
+            validator.commit(player, commitment, 1).unwrap();
+        }
+        validator.reveal(Player::One, Move::Cooperate, &salt, 2).unwrap();
+        validator.reveal(Player::Two, Move::Cooperate, &salt, 3).unwrap().unwrap();
+
+        // Long after the last reveal, no deadline can skip the draw
+        let pending = "Continuation draw for round 1 is pending";
+        assert_eq!(validator.resolve_timeout(50).unwrap_err(), pending);
+        assert_eq!(validator.commit(Player::One, [1u8; 32], 50).unwrap_err(), pending);
+        assert_eq!(validator.get_state().round, 1);
+
+        // The next commit phase runs from the draw
+        assert_eq!(validator.draw_continuation(&beacon(1, 1), 60), Ok(true));
+        assert!(validator.resolve_timeout(70).unwrap().is_none());
+        let commitment_1 = commitment(1, Player::One, Move::Cooperate, &salt);
+        validator.commit(Player::One, commitment_1, 70).unwrap();
+        assert!(validator.resolve_timeout(71).unwrap().is_none());
+        assert!(validator.get_state().commit_reveal.as_ref().unwrap().revealing);
+    }
+
+    #[test]
+    fn test_reputation_across_opponents() {
+        // 20/20 against one opponent, 0/10 against another: each opponent counts equally
+        let rep = PlayerReputation::calculate_across_opponents(
+            "tb1q...".to_string(),
+            &[(20, 20), (10, 0)],
+        );
+        assert_eq!(rep.total_moves, 30);
+        assert_eq!(rep.cooperative_moves, 20);
+        assert_eq!(rep.reputation_score, 50);
+
+        // Opponents with few moves count for less: one-move sock puppets cannot
+        // outweigh a real match
+        let rep = PlayerReputation::calculate_across_opponents(
+            "tb1q...".to_string(),
+            &[(10, 0), (1, 1), (1, 1), (1, 1), (1, 1), (1, 1)],
+        );
+        assert_eq!(rep.reputation_score, 33);
+
+        // A single opponent matches the flat calculation
+        let single =
+            PlayerReputation::calculate_across_opponents("tb1q...".to_string(), &[(10, 6)]);
+        assert_eq!(single.reputation_score, 60);
+        assert_eq!(single.voting_power, 60);
+
+        // Opponents without moves are ignored
+        let rep = PlayerReputation::calculate_across_opponents("tb1q...".to_string(), &[(0, 0)]);
+        assert_eq!(rep.reputation_score, 50);
+    }
+
+    #[test]
+    fn test_minimum_activity_per_tier() {
+        // One cooperative move: a perfect score, but too little activity for Trusted
+        let rep = PlayerReputation::calculate_from_moves("tb1q...".to_string(), 1, 1);
+        assert_eq!((rep.reputation_score, rep.tier, rep.voting_power), (100, 1, 100));
+        let rep = PlayerReputation::calculate_from_moves("tb1q...".to_string(), 10, 10);
+        assert_eq!(rep.tier, 2);
+
+        let config = ReputationConfig {
+            min_moves: [0, 5, 20],
+            min_games: [0, 1, 3],
+            ..ReputationConfig::default()
+        };
+        let calculate = |opponents: &[(u32, u32)], games| {
+            let address = "tb1q...".to_string();
+            PlayerReputation::calculate_with_config(address, opponents, games, &config)
+        };
+
+        // Too little activity for any tier above Suspicious, whatever the score
+        let rep = calculate(&[(1, 1)], 1);
+        assert_eq!((rep.reputation_score, rep.tier, rep.voting_power), (100, 0, 50));
+
+        // Enough moves, but too few games for Trusted
+        assert_eq!(calculate(&[(30, 30)], 2).tier, 1);
+        assert_eq!(calculate(&[(10, 10), (10, 10), (10, 10)], 3).tier, 2);
+    }
+
+    #[test]
+    fn test_small_sample_scoring() {
+        let bayesian = ScoringMethod::Bayesian { prior_moves: 10 };
+        let wilson = ScoringMethod::WilsonBound { z: 1.96 };
+
+        // One cooperative move is pulled toward neutral
+        assert_eq!(ScoringMethod::CooperationRate.score(Some(1.0), 1), 100);
+        assert_eq!(bayesian.score(Some(1.0), 1), 55);
+        assert_eq!(wilson.score(Some(1.0), 1), 50);
+
+        // Large samples keep their rate, on either side of neutral
+        assert_eq!(bayesian.score(Some(1.0), 1_000), 100);
+        assert_eq!(wilson.score(Some(1.0), 100), 96);
+        assert_eq!(wilson.score(Some(0.0), 100), 4);
+
+        // No moves: neutral
+        assert_eq!(wilson.score(None, 0), 50);
+
+        let config = ReputationConfig {
+            scoring: bayesian,
+            ..ReputationConfig::default()
+        };
+        let rep =
+            PlayerReputation::calculate_with_config("tb1q...".to_string(), &[(1, 1)], 1, &config);
+        assert_eq!((rep.reputation_score, rep.tier), (55, 1));
+
+        // Each opponent is shrunk by its own sample: twenty one-move opponents are
+        // twenty small samples, not one sample of twenty moves
+        let address = "tb1q...".to_string();
+        let rep = PlayerReputation::calculate_with_config(address, &[(1, 1); 20], 20, &config);
+        assert_eq!(rep.reputation_score, 55);
+        let rep = PlayerReputation::calculate_with_config(
+            "tb1q...".to_string(),
+            &[(20, 20), (20, 0)],
+            2,
+            &config,
+        );
+        assert_eq!(rep.reputation_score, 50);
+    }
+
     #[test]
     fn test_reputation_no_moves() {
         // No moves = Neutral (50%)
@@ -457,4 +2009,45 @@ mod tests {
         assert_eq!(rep.tier, 1); // Neutral
         assert_eq!(rep.voting_power, 50);
     }
+
+    #[test]
+    fn test_move_history_packing() {
+        let history = MoveHistory::from_values(&[0, 0, 1, 0, 1, 0, 0, 0, 0, 1]).unwrap();
+
+        assert_eq!(history.len(), 10);
+        assert_eq!(history.packed(), &[0b0001_0100, 0b0000_0010]);
+        assert_eq!(history.get(2), Some(Move::Defect));
+        assert_eq!(history.get(3), Some(Move::Cooperate));
+        assert_eq!(history.get(10), None);
+        assert_eq!(history.defections(), 3);
+        assert_eq!(history.cooperations(), 7);
+
+        let moves: Vec<Move> = history.iter().collect();
+        assert_eq!(moves.len(), 10);
+        assert_eq!(moves.iter().copied().collect::<MoveHistory>(), history);
+
+        assert!(MoveHistory::from_values(&[0, 2]).is_err());
+    }
+
+    #[test]
+    fn test_move_history_packed_validation() {
+        assert!(MoveHistory::from_packed(3, vec![0b0000_0101]).is_ok());
+
+        // Wrong byte count for the move count
+        assert!(MoveHistory::from_packed(9, vec![0]).is_err());
+
+        // Non-zero padding bits
+        assert!(MoveHistory::from_packed(3, vec![0b0000_1000]).is_err());
+    }
+
+    #[test]
+    fn test_move_history_json() {
+        // Plain 0/1 lists are accepted and serialized in packed form
+        let history: MoveHistory = serde_json::from_str("[0, 0, 1, 0, 1]").unwrap();
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(json, r#"{"len":5,"packed":"14"}"#);
+        assert_eq!(serde_json::from_str::<MoveHistory>(&json).unwrap(), history);
+
+        assert!(serde_json::from_str::<MoveHistory>("[0, 3]").is_err());
+    }
 }
@@ -1,52 +1,78 @@
+#![cfg_attr(not(feature = "json-io"), no_main)]
+
 /**
  * CHARMS ZKVM ENTRYPOINT
- * 
+ *
  * This is the main binary executed by Charms zkVM for proving game moves.
  * The zkVM will:
  * 1. Load game history from witness data
  * 2. Execute this program with that history
  * 3. Generate a cryptographic proof of correct execution
  * 4. Embed proof in witness data on-chain
- * 
+ *
+ * I/O modes:
+ * - Default (SP1 guest): reads the compact binary `ProveInput` with
+ *   `sp1_zkvm::io::read_vec` and commits `ProveOutput::to_public_values`.
+ *   Each phase in `CYCLE_PHASES` is wrapped in an SP1 cycle-tracker report.
+ *   If the input carries a previous proof, it is verified with SP1 recursion
+ *   (`verify_sp1_proof`) before the new session is folded in.
+ *   Signed game transcripts have both players' Schnorr signatures checked during
+ *   move validation.
+ * - `json-io` feature (native testing): JSON on stdin, JSON on stdout
+ *
+ * Native usage:
+ *   cargo run --features json-io < test_input.json
+ *
  * Usage (via Charms):
  *   charms spell check --app-bins=target/release/trust-game ...
  */
 
-use serde::{Deserialize, Serialize};
-use std::io::Read;
-use trust_game::PlayerReputation;
+use trust_game::prove::ProveInput;
 
-/// Input to the zkVM: game history to prove
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProveInput {
-    /// Player's Bitcoin address
-    pub player_address: String,
-    /// Sequence of moves made by the player
-    pub moves: Vec<u32>, // 0 = Cooperate, 1 = Defect
-    /// Opponent's moves (for context)
-    pub opponent_moves: Vec<u32>,
-    /// Payoff matrix [R, T, S, P]
-    pub payoffs: [i32; 4],
-}
+#[cfg(not(feature = "json-io"))]
+sp1_zkvm::entrypoint!(main);
+
+#[cfg(not(feature = "json-io"))]
+fn main() {
+    use trust_game::encoding::sha256;
+    use trust_game::prove::{reputation_output, validate_history, validate_previous};
+
+    // Read compact binary input from the zkVM
+    println!("cycle-tracker-report-start: deserialization");
+    let input_data = sp1_zkvm::io::read_vec();
+    let input = ProveInput::decode(&input_data).expect("Failed to deserialize input");
+    println!("cycle-tracker-report-end: deserialization");
 
-/// Output from the zkVM: verified reputation
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProveOutput {
-    /// Player's address
-    pub player_address: String,
-    /// Total moves
-    pub total_moves: u32,
-    /// Cooperative moves
-    pub cooperative_moves: u32,
-    /// Calculated reputation score (0-100)
-    pub reputation_score: u32,
-    /// Reputation tier (0=Suspicious, 1=Neutral, 2=Trusted)
-    pub tier: u8,
-    /// Voting power
-    pub voting_power: u32,
+    // Verify the previous proof this session builds on
+    println!("cycle-tracker-report-start: previous_proof");
+    if let Some(previous) = &input.previous {
+        let public_values_digest = sha256(&previous.output.to_public_values());
+        sp1_zkvm::lib::verify::verify_sp1_proof(&previous.vkey_digest, &public_values_digest);
+    }
+    validate_previous(&input).expect("Invalid previous proof");
+    println!("cycle-tracker-report-end: previous_proof");
+
+    // Validate moves and payoff matrix
+    println!("cycle-tracker-report-start: move_validation");
+    let records = validate_history(&input).expect("Invalid game history");
+    println!("cycle-tracker-report-end: move_validation");
+
+    // Calculate reputation
+    println!("cycle-tracker-report-start: reputation");
+    let output = reputation_output(&input, records).expect("Invalid game history");
+    println!("cycle-tracker-report-end: reputation");
+
+    // Commit spell data and spell commitment as public values
+    println!("cycle-tracker-report-start: output");
+    sp1_zkvm::io::commit_slice(&output.to_public_values());
+    println!("cycle-tracker-report-end: output");
 }
 
+#[cfg(feature = "json-io")]
 fn main() {
+    use std::io::Read;
+    use trust_game::prove::prove;
+
     // Read input from stdin
     let mut input_data = Vec::new();
     std::io::stdin()
@@ -57,50 +83,8 @@ fn main() {
     let input: ProveInput = serde_json::from_slice(&input_data)
         .expect("Failed to deserialize input");
 
-    // Validate and prove move correctness
-    let mut cooperative_count = 0;
-
-    for move_val in &input.moves {
-        if *move_val == 0 {
-            cooperative_count += 1;
-        }
-        assert!(
-            *move_val == 0 || *move_val == 1,
-            "Invalid move: must be 0 (Cooperate) or 1 (Defect)"
-        );
-    }
-
-    // Validate payoff matrix (typical PD constraints)
-    assert!(
-        input.payoffs[1] > input.payoffs[0],
-        "Temptation (T) must be > Reward (R)"
-    );
-    assert!(
-        input.payoffs[0] > input.payoffs[3],
-        "Reward (R) must be > Punishment (P)"
-    );
-    assert!(
-        input.payoffs[3] > input.payoffs[2],
-        "Punishment (P) must be > Sucker (S)"
-    );
-
-    // Calculate reputation
-    let total_moves = input.moves.len() as u32;
-    let reputation = PlayerReputation::calculate_from_moves(
-        input.player_address.clone(),
-        total_moves,
-        cooperative_count as u32,
-    );
-
-    // Create output
-    let output = ProveOutput {
-        player_address: input.player_address,
-        total_moves,
-        cooperative_moves: cooperative_count as u32,
-        reputation_score: reputation.reputation_score,
-        tier: reputation.tier,
-        voting_power: reputation.voting_power,
-    };
+    // Validate game history and calculate reputation
+    let output = prove(&input).expect("Invalid game history");
 
     // Write output to stdout
     let output_json = serde_json::to_vec(&output).expect("Failed to serialize output");
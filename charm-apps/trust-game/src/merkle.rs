@@ -0,0 +1,218 @@
+/**
+ * MERKLE TREES AND INCLUSION PROOFS
+ *
+ * Binary SHA-256 Merkle tree used to commit to the votes in a voting round.
+ * A voter can prove their vote was counted with a short inclusion proof
+ * instead of downloading every vote.
+ *
+ * Construction:
+ * - Leaf hash:     SHA-256(0x00 || leaf data)
+ * - Interior hash: SHA-256(0x01 || left || right)
+ * - An odd node at the end of a level is promoted unchanged (never duplicated)
+ * - The root of an empty tree is 32 zero bytes
+ *
+ * Only depends on SHA-256, so verification runs natively and inside the zkVM.
+ */
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Root of a tree with no leaves
+pub const EMPTY_ROOT: [u8; 32] = [0u8; 32];
+
+/// Hash raw leaf data into a leaf node
+pub fn hash_leaf(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Hash two child nodes into their parent
+pub fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Proof that a leaf is included under a Merkle root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the leaf in the tree
+    pub leaf_index: u32,
+    /// Number of leaves in the tree
+    pub leaf_count: u32,
+    /// Sibling hashes from the leaf level up to the root
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// Merkle tree over pre-hashed leaves
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// Every level of the tree, leaves first, root last
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Build a tree from leaf hashes (see `hash_leaf`)
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        MerkleTree { levels }
+    }
+
+    /// Number of leaves in the tree
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Root hash (EMPTY_ROOT for an empty tree)
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1]
+            .first()
+            .copied()
+            .unwrap_or(EMPTY_ROOT)
+    }
+
+    /// Generate an inclusion proof for the leaf at `index`
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut position = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            position /= 2;
+        }
+
+        Some(MerkleProof {
+            leaf_index: index as u32,
+            leaf_count: self.leaf_count() as u32,
+            siblings,
+        })
+    }
+}
+
+/// Verify that `leaf` (a leaf hash) is included under `root`
+pub fn verify_proof(root: &[u8; 32], leaf: &[u8; 32], proof: &MerkleProof) -> bool {
+    if proof.leaf_index >= proof.leaf_count {
+        return false;
+    }
+
+    let mut hash = *leaf;
+    let mut position = proof.leaf_index;
+    let mut width = proof.leaf_count;
+    let mut siblings = proof.siblings.iter();
+
+    while width > 1 {
+        let is_right = position % 2 == 1;
+        let has_sibling = is_right || position + 1 < width;
+
+        if has_sibling {
+            let sibling = match siblings.next() {
+                Some(sibling) => sibling,
+                None => return false,
+            };
+            hash = if is_right {
+                hash_node(sibling, &hash)
+            } else {
+                hash_node(&hash, sibling)
+            };
+        }
+
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+
+    // Every sibling must be consumed
+    siblings.next().is_none() && hash == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| hash_leaf(&[i])).collect()
+    }
+
+    #[test]
+    fn test_empty_and_single_leaf() {
+        assert_eq!(MerkleTree::new(Vec::new()).root(), EMPTY_ROOT);
+
+        let tree = MerkleTree::new(leaves(1));
+        assert_eq!(tree.root(), hash_leaf(&[0]));
+
+        let proof = tree.proof(0).unwrap();
+        assert!(proof.siblings.is_empty());
+        assert!(verify_proof(&tree.root(), &hash_leaf(&[0]), &proof));
+    }
+
+    #[test]
+    fn test_odd_leaf_is_promoted() {
+        let l = leaves(3);
+        let tree = MerkleTree::new(l.clone());
+
+        assert_eq!(tree.root(), hash_node(&hash_node(&l[0], &l[1]), &l[2]));
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf() {
+        for count in 1..=9 {
+            let l = leaves(count);
+            let tree = MerkleTree::new(l.clone());
+
+            for (index, leaf) in l.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(verify_proof(&tree.root(), leaf, &proof));
+            }
+            assert!(tree.proof(count as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn test_tampered_proof_rejected() {
+        let l = leaves(5);
+        let tree = MerkleTree::new(l.clone());
+        let proof = tree.proof(2).unwrap();
+
+        // Wrong leaf
+        assert!(!verify_proof(&tree.root(), &l[3], &proof));
+
+        // Wrong index
+        let mut moved = proof.clone();
+        moved.leaf_index = 3;
+        assert!(!verify_proof(&tree.root(), &l[2], &moved));
+
+        // Extra sibling
+        let mut padded = proof.clone();
+        padded.siblings.push([0u8; 32]);
+        assert!(!verify_proof(&tree.root(), &l[2], &padded));
+
+        // Corrupted sibling
+        let mut corrupted = proof;
+        corrupted.siblings[0][0] ^= 1;
+        assert!(!verify_proof(&tree.root(), &l[2], &corrupted));
+    }
+}
@@ -0,0 +1,272 @@
+/**
+ * MIXED STRATEGIES
+ *
+ * A mixed strategy cooperates with some probability instead of always playing
+ * the same move. Used for research and for the "Random" strategy.
+ *
+ * - Expected payoffs of one mixed strategy against another
+ * - Nash equilibria of 2x2 games (pure and fully mixed)
+ *
+ * The equilibrium solver reports isolated equilibria only: every pure profile
+ * where neither player gains by switching, plus the fully mixed equilibrium
+ * where each player is indifferent between their two moves. Degenerate games
+ * with ties can have a continuum of equilibria, which is not enumerated.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AsymmetricPayoffMatrix, Move, PayoffMatrix};
+
+/// Strategy that cooperates with a fixed probability each round
+/// Serialized as the bare probability
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct MixedStrategy {
+    /// Probability of cooperating (0.0 - 1.0)
+    cooperate_probability: f64,
+}
+
+impl MixedStrategy {
+    /// Create a strategy cooperating with probability `cooperate_probability`
+    pub fn new(cooperate_probability: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&cooperate_probability) {
+            return Err(format!(
+                "Cooperation probability must be between 0 and 1, got {}",
+                cooperate_probability
+            ));
+        }
+        Ok(MixedStrategy {
+            cooperate_probability,
+        })
+    }
+
+    /// Strategy that always plays `move_val`
+    pub fn pure(move_val: Move) -> Self {
+        let cooperate_probability = match move_val {
+            Move::Cooperate => 1.0,
+            Move::Defect => 0.0,
+        };
+        MixedStrategy {
+            cooperate_probability,
+        }
+    }
+
+    /// Uniformly random play, as used by the "Random" strategy
+    pub fn uniform() -> Self {
+        MixedStrategy {
+            cooperate_probability: 0.5,
+        }
+    }
+
+    pub fn cooperate_probability(&self) -> f64 {
+        self.cooperate_probability
+    }
+
+    /// Probability of playing `move_val`
+    pub fn probability(&self, move_val: Move) -> f64 {
+        match move_val {
+            Move::Cooperate => self.cooperate_probability,
+            Move::Defect => 1.0 - self.cooperate_probability,
+        }
+    }
+
+    /// The pure move this strategy always plays, if it is pure
+    pub fn as_pure(&self) -> Option<Move> {
+        if self.cooperate_probability == 1.0 {
+            Some(Move::Cooperate)
+        } else if self.cooperate_probability == 0.0 {
+            Some(Move::Defect)
+        } else {
+            None
+        }
+    }
+
+    /// Pick a move given a uniform sample in [0, 1)
+    pub fn play(&self, uniform_sample: f64) -> Move {
+        if uniform_sample < self.cooperate_probability {
+            Move::Cooperate
+        } else {
+            Move::Defect
+        }
+    }
+
+    /// Expected payoff of this strategy against `opponent` in a symmetric game
+    pub fn expected_payoff(&self, opponent: &MixedStrategy, payoff_matrix: &PayoffMatrix) -> f64 {
+        let payoffs = AsymmetricPayoffMatrix::symmetric(payoff_matrix.clone());
+        expected_payoffs(self, opponent, &payoffs).0
+    }
+}
+
+impl TryFrom<f64> for MixedStrategy {
+    type Error = String;
+
+    fn try_from(cooperate_probability: f64) -> Result<Self, String> {
+        MixedStrategy::new(cooperate_probability)
+    }
+}
+
+impl From<MixedStrategy> for f64 {
+    fn from(strategy: MixedStrategy) -> Self {
+        strategy.cooperate_probability
+    }
+}
+
+const MOVES: [Move; 2] = [Move::Cooperate, Move::Defect];
+
+/// Expected payoffs for player 1 and player 2
+pub fn expected_payoffs(
+    strategy_1: &MixedStrategy,
+    strategy_2: &MixedStrategy,
+    payoffs: &AsymmetricPayoffMatrix,
+) -> (f64, f64) {
+    let mut expected = (0.0, 0.0);
+
+    for move_1 in MOVES {
+        for move_2 in MOVES {
+            let probability = strategy_1.probability(move_1) * strategy_2.probability(move_2);
+            let (payoff_1, payoff_2) = payoffs.payoffs(move_1, move_2);
+            expected.0 += probability * payoff_1 as f64;
+            expected.1 += probability * payoff_2 as f64;
+        }
+    }
+
+    expected
+}
+
+/// Cooperation probability that leaves a player with `matrix` indifferent between
+/// their moves, if it lies strictly between 0 and 1
+fn indifference_probability(matrix: &PayoffMatrix) -> Option<f64> {
+    // q*R + (1-q)*S = q*T + (1-q)*P
+    let denominator = (matrix.r - matrix.t) + (matrix.p - matrix.s);
+    if denominator == 0 {
+        return None;
+    }
+
+    let q = (matrix.p - matrix.s) as f64 / denominator as f64;
+    if q > 0.0 && q < 1.0 {
+        Some(q)
+    } else {
+        None
+    }
+}
+
+/// Isolated Nash equilibria of a 2x2 game as (player 1, player 2) strategy pairs
+/// Pure equilibria come first, in (C,C), (C,D), (D,C), (D,D) order.
+/// A symmetric `PayoffMatrix` can be passed with `.into()`.
+pub fn nash_equilibria(payoffs: &AsymmetricPayoffMatrix) -> Vec<(MixedStrategy, MixedStrategy)> {
+    let flip = |move_val: Move| match move_val {
+        Move::Cooperate => Move::Defect,
+        Move::Defect => Move::Cooperate,
+    };
+
+    let mut equilibria = Vec::new();
+
+    // Pure: neither player gains by switching their own move
+    for move_1 in MOVES {
+        for move_2 in MOVES {
+            let (payoff_1, payoff_2) = payoffs.payoffs(move_1, move_2);
+            let (deviation_1, _) = payoffs.payoffs(flip(move_1), move_2);
+            let (_, deviation_2) = payoffs.payoffs(move_1, flip(move_2));
+
+            if payoff_1 >= deviation_1 && payoff_2 >= deviation_2 {
+                equilibria.push((MixedStrategy::pure(move_1), MixedStrategy::pure(move_2)));
+            }
+        }
+    }
+
+    // Fully mixed: each player mixes so the OTHER player is indifferent
+    if let (Some(p), Some(q)) = (
+        indifference_probability(&payoffs.player_2),
+        indifference_probability(&payoffs.player_1),
+    ) {
+        equilibria.push((
+            MixedStrategy {
+                cooperate_probability: p,
+            },
+            MixedStrategy {
+                cooperate_probability: q,
+            },
+        ));
+    }
+
+    equilibria
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probabilities(equilibria: &[(MixedStrategy, MixedStrategy)]) -> Vec<(f64, f64)> {
+        equilibria
+            .iter()
+            .map(|(s1, s2)| (s1.cooperate_probability(), s2.cooperate_probability()))
+            .collect()
+    }
+
+    #[test]
+    fn test_mixed_strategy_bounds() {
+        assert!(MixedStrategy::new(0.25).is_ok());
+        assert!(MixedStrategy::new(1.5).is_err());
+        assert!(MixedStrategy::new(-0.1).is_err());
+        assert!(MixedStrategy::new(f64::NAN).is_err());
+
+        assert_eq!(MixedStrategy::pure(Move::Defect).as_pure(), Some(Move::Defect));
+        assert_eq!(MixedStrategy::uniform().as_pure(), None);
+
+        let strategy = MixedStrategy::new(0.25).unwrap();
+        assert_eq!(strategy.play(0.1), Move::Cooperate);
+        assert_eq!(strategy.play(0.25), Move::Defect);
+
+        assert_eq!(serde_json::to_string(&strategy).unwrap(), "0.25");
+        assert!(serde_json::from_str::<MixedStrategy>("2.0").is_err());
+    }
+
+    #[test]
+    fn test_expected_payoff() {
+        let matrix = PayoffMatrix::default();
+        let cooperate = MixedStrategy::pure(Move::Cooperate);
+        let defect = MixedStrategy::pure(Move::Defect);
+
+        // Pure strategies reproduce the matrix
+        assert_eq!(cooperate.expected_payoff(&cooperate, &matrix), 2.0);
+        assert_eq!(cooperate.expected_payoff(&defect, &matrix), -1.0);
+        assert_eq!(defect.expected_payoff(&cooperate, &matrix), 3.0);
+
+        // Random vs. Random: (2 - 1 + 3 + 0) / 4
+        let random = MixedStrategy::uniform();
+        assert_eq!(random.expected_payoff(&random, &matrix), 1.0);
+    }
+
+    #[test]
+    fn test_prisoners_dilemma_equilibrium() {
+        // Mutual defection is the only equilibrium
+        let equilibria = nash_equilibria(&PayoffMatrix::default().into());
+        assert_eq!(probabilities(&equilibria), vec![(0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_stag_hunt_equilibria() {
+        let stag_hunt = PayoffMatrix { r: 4, s: -1, t: 3, p: 0 };
+        let equilibria = nash_equilibria(&stag_hunt.into());
+
+        // Both coordinate on stag, both on hare, or both mix 50/50
+        assert_eq!(probabilities(&equilibria), vec![(1.0, 1.0), (0.0, 0.0), (0.5, 0.5)]);
+    }
+
+    #[test]
+    fn test_chicken_equilibria() {
+        let chicken = AsymmetricPayoffMatrix::from(PayoffMatrix { r: 2, s: 0, t: 3, p: -1 });
+        let equilibria = nash_equilibria(&chicken);
+
+        // One swerves while the other doesn't, or both mix
+        assert_eq!(probabilities(&equilibria), vec![(1.0, 0.0), (0.0, 1.0), (0.5, 0.5)]);
+
+        // In the mixed equilibrium both players are indifferent between moves
+        let (mixed_1, mixed_2) = equilibria[2];
+        let against_mixed =
+            |move_val| expected_payoffs(&MixedStrategy::pure(move_val), &mixed_2, &chicken).0;
+        let (cooperate, defect) = (against_mixed(Move::Cooperate), against_mixed(Move::Defect));
+        assert_eq!(cooperate, defect);
+        assert_eq!(mixed_1, mixed_2);
+    }
+}
@@ -0,0 +1,378 @@
+/**
+ * N-PLAYER GAMES
+ *
+ * Consensus involves many validators, not two. This module plays an N-player
+ * Prisoner's Dilemma: every round each player cooperates or defects, and each
+ * player's payoff depends only on their own move and on how many of the OTHER
+ * players cooperated.
+ *
+ * Payoffs are linear in the number of cooperating others `k`:
+ * - Cooperator: cooperate_base + cooperate_bonus * k
+ * - Defector:   defect_base + defect_bonus * k
+ *
+ * This covers the public-goods game (each cooperator adds to a shared pot that
+ * everyone receives) as well as linear N-player PDs. It is a dilemma when
+ * defecting always pays more than cooperating, yet everyone cooperating beats
+ * everyone defecting.
+ *
+ * A player's reputation is computed from their moves across all opponents,
+ * exactly like the two-player game.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::{Move, PlayerReputation};
+
+/// Payoff function based on the number of other cooperators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NPlayerPayoffs {
+    /// Cooperator's payoff when nobody else cooperates
+    pub cooperate_base: i32,
+    /// Extra payoff a cooperator gets per other cooperator
+    pub cooperate_bonus: i32,
+    /// Defector's payoff when nobody else cooperates
+    pub defect_base: i32,
+    /// Extra payoff a defector gets per other cooperator
+    pub defect_bonus: i32,
+}
+
+impl Default for NPlayerPayoffs {
+    fn default() -> Self {
+        // Public goods: cooperating costs 1, and every other cooperator gives everyone 2
+        NPlayerPayoffs {
+            cooperate_base: -1,
+            cooperate_bonus: 2,
+            defect_base: 0,
+            defect_bonus: 2,
+        }
+    }
+}
+
+impl NPlayerPayoffs {
+    /// Payoff for a player's move when `other_cooperators` others cooperated
+    /// Errors if the payoff does not fit in an i32.
+    pub fn payoff(&self, player_move: Move, other_cooperators: u32) -> Result<i32, String> {
+        let (base, bonus) = match player_move {
+            Move::Cooperate => (self.cooperate_base, self.cooperate_bonus),
+            Move::Defect => (self.defect_base, self.defect_bonus),
+        };
+        i32::try_from(other_cooperators)
+            .ok()
+            .and_then(|k| bonus.checked_mul(k))
+            .and_then(|bonus| base.checked_add(bonus))
+            .ok_or(format!("Payoff overflows with {} other cooperators", other_cooperators))
+    }
+
+    /// Check the N-player dilemma conditions for `player_count` players
+    pub fn validate(&self, player_count: u32) -> Result<(), String> {
+        if player_count < 2 {
+            return Err("An N-player game needs at least 2 players".to_string());
+        }
+
+        let others = player_count - 1;
+
+        // Payoffs are linear in k, so checking both ends covers every k
+        // (including overflow: payoffs in between lie between the two ends)
+        for k in [0, others] {
+            if self.payoff(Move::Defect, k)? <= self.payoff(Move::Cooperate, k)? {
+                return Err(format!(
+                    "Defecting must pay more than cooperating (fails with {} other cooperators)",
+                    k
+                ));
+            }
+        }
+
+        if self.payoff(Move::Cooperate, others)? <= self.payoff(Move::Defect, 0)? {
+            return Err("Everyone cooperating must beat everyone defecting".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Game state for a repeated N-player game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NPlayerGameState {
+    /// Round number (0-indexed)
+    pub round: u32,
+    /// Total rounds in this game
+    pub total_rounds: u32,
+    /// Payoff function
+    pub payoffs: NPlayerPayoffs,
+    /// Bitcoin address of each player
+    pub players: Vec<String>,
+    /// Each player's cumulative score, in `players` order
+    pub scores: Vec<i32>,
+    /// Each player's move history, in `players` order
+    pub histories: Vec<Vec<Move>>,
+}
+
+impl NPlayerGameState {
+    /// Create a new game with the default public-goods payoffs
+    pub fn new(players: Vec<String>, total_rounds: u32) -> Self {
+        NPlayerGameState::new_with_payoffs(players, total_rounds, NPlayerPayoffs::default())
+    }
+
+    /// Create a new game with a custom payoff function
+    pub fn new_with_payoffs(
+        players: Vec<String>,
+        total_rounds: u32,
+        payoffs: NPlayerPayoffs,
+    ) -> Self {
+        let player_count = players.len();
+        NPlayerGameState {
+            round: 0,
+            total_rounds,
+            payoffs,
+            players,
+            scores: vec![0; player_count],
+            histories: vec![Vec::new(); player_count],
+        }
+    }
+
+    pub fn player_count(&self) -> u32 {
+        self.players.len() as u32
+    }
+
+    /// Index of a player by address
+    pub fn player_index(&self, address: &str) -> Option<usize> {
+        self.players.iter().position(|p| p == address)
+    }
+
+    /// Reputation from a player's moves against all other players
+    pub fn reputation(&self, address: &str) -> Option<PlayerReputation> {
+        let history = &self.histories[self.player_index(address)?];
+        let cooperative_moves = history.iter().filter(|m| **m == Move::Cooperate).count();
+
+        Some(PlayerReputation::calculate_from_moves(
+            address.to_string(),
+            history.len() as u32,
+            cooperative_moves as u32,
+        ))
+    }
+}
+
+/// Outcome of a single N-player round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NPlayerRoundOutcome {
+    /// Each player's move, in `players` order
+    pub moves: Vec<Move>,
+    /// Each player's payoff, in `players` order
+    pub payoffs: Vec<i32>,
+    /// Number of players who cooperated
+    pub cooperators: u32,
+}
+
+/// Payoffs for every player given one round of moves
+pub fn get_n_player_payoffs(
+    moves: &[Move],
+    payoffs: &NPlayerPayoffs,
+) -> Result<Vec<i32>, String> {
+    let cooperators = moves.iter().filter(|m| **m == Move::Cooperate).count() as u32;
+
+    moves
+        .iter()
+        .map(|player_move| {
+            // Exclude the player's own cooperation from the count
+            let others = match player_move {
+                Move::Cooperate => cooperators - 1,
+                Move::Defect => cooperators,
+            };
+            payoffs.payoff(*player_move, others)
+        })
+        .collect()
+}
+
+/// Validates and plays rounds of an N-player game
+pub struct NPlayerRoundValidator {
+    pub state: NPlayerGameState,
+}
+
+impl NPlayerRoundValidator {
+    /// Create a validator, checking the players and the payoff function for this many players
+    pub fn new(state: NPlayerGameState) -> Result<Self, String> {
+        // Each address is one seat: a duplicate would merge two players' reputations
+        let mut seen = BTreeSet::new();
+        for address in &state.players {
+            if !seen.insert(address) {
+                return Err(format!("Duplicate player address {}", address));
+            }
+        }
+        state.payoffs.validate(state.player_count())?;
+        Ok(NPlayerRoundValidator { state })
+    }
+
+    /// Execute and validate a round (one move per player, in `players` order)
+    pub fn play_round(&mut self, moves: &[Move]) -> Result<NPlayerRoundOutcome, String> {
+        if self.is_finished() {
+            return Err("Game already finished".to_string());
+        }
+
+        if moves.len() != self.state.players.len() {
+            return Err(format!(
+                "Expected {} moves, got {}",
+                self.state.players.len(),
+                moves.len()
+            ));
+        }
+
+        let payoffs = get_n_player_payoffs(moves, &self.state.payoffs)?;
+        let scores = self
+            .state
+            .scores
+            .iter()
+            .zip(&payoffs)
+            .map(|(score, payoff)| score.checked_add(*payoff))
+            .collect::<Option<Vec<i32>>>()
+            .ok_or("Score overflow".to_string())?;
+
+        for (i, player_move) in moves.iter().enumerate() {
+            self.state.histories[i].push(*player_move);
+        }
+        self.state.scores = scores;
+        self.state.round += 1;
+
+        Ok(NPlayerRoundOutcome {
+            moves: moves.to_vec(),
+            payoffs,
+            cooperators: moves.iter().filter(|m| **m == Move::Cooperate).count() as u32,
+        })
+    }
+
+    /// Get current game state
+    pub fn get_state(&self) -> &NPlayerGameState {
+        &self.state
+    }
+
+    /// Check if game is finished
+    pub fn is_finished(&self) -> bool {
+        self.state.round >= self.state.total_rounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("tb1qplayer{}", i)).collect()
+    }
+
+    #[test]
+    fn test_payoffs_by_cooperator_count() {
+        let payoffs = NPlayerPayoffs::default();
+        let moves = [Move::Cooperate, Move::Cooperate, Move::Defect, Move::Cooperate];
+
+        // Cooperators see 2 other cooperators, the defector sees 3
+        assert_eq!(get_n_player_payoffs(&moves, &payoffs).unwrap(), vec![3, 3, 6, 3]);
+
+        // Everyone defecting: nobody gains
+        assert_eq!(get_n_player_payoffs(&[Move::Defect; 3], &payoffs).unwrap(), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_dilemma_conditions() {
+        assert!(NPlayerPayoffs::default().validate(5).is_ok());
+        assert!(NPlayerPayoffs::default().validate(1).is_err());
+
+        // Cooperation dominates: not a dilemma
+        let harmony = NPlayerPayoffs {
+            cooperate_base: 1,
+            ..NPlayerPayoffs::default()
+        };
+        assert!(harmony.validate(5).is_err());
+
+        // Full cooperation no better than full defection
+        let no_gain = NPlayerPayoffs {
+            cooperate_bonus: 0,
+            defect_bonus: 0,
+            ..NPlayerPayoffs::default()
+        };
+        assert!(no_gain.validate(5).is_err());
+
+        // Payoffs that overflow an i32 are rejected rather than wrapping
+        let huge = NPlayerPayoffs {
+            cooperate_bonus: i32::MAX,
+            defect_bonus: i32::MAX,
+            ..NPlayerPayoffs::default()
+        };
+        assert_eq!(
+            huge.validate(5).unwrap_err(),
+            "Payoff overflows with 4 other cooperators"
+        );
+        assert!(huge.payoff(Move::Defect, 1).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_duplicate_players() {
+        let mut addresses = players(3);
+        addresses.push("tb1qplayer1".to_string());
+        let state = NPlayerGameState::new(addresses, 2);
+        assert_eq!(
+            NPlayerRoundValidator::new(state).err(),
+            Some("Duplicate player address tb1qplayer1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_score_overflow() {
+        let mut state = NPlayerGameState::new(players(3), 2);
+        state.scores[2] = i32::MAX;
+        let mut validator = NPlayerRoundValidator::new(state).unwrap();
+
+        assert_eq!(
+            validator.play_round(&[Move::Cooperate, Move::Cooperate, Move::Defect]),
+            Err("Score overflow".to_string())
+        );
+        assert_eq!(validator.get_state().round, 0);
+        assert!(validator.get_state().histories[0].is_empty());
+    }
+
+    #[test]
+    fn test_n_player_rounds() {
+        let state = NPlayerGameState::new(players(3), 2);
+        let mut validator = NPlayerRoundValidator::new(state).unwrap();
+
+        let outcome = validator
+            .play_round(&[Move::Cooperate, Move::Cooperate, Move::Defect])
+            .unwrap();
+        assert_eq!(outcome.cooperators, 2);
+        assert_eq!(outcome.payoffs, vec![1, 1, 4]);
+
+        // Wrong number of moves
+        assert!(validator.play_round(&[Move::Cooperate]).is_err());
+
+        validator.play_round(&[Move::Cooperate; 3]).unwrap();
+        assert_eq!(validator.get_state().scores, vec![4, 4, 7]);
+        assert!(validator.is_finished());
+        assert!(validator.play_round(&[Move::Cooperate; 3]).is_err());
+    }
+
+    #[test]
+    fn test_reputation_across_opponents() {
+        let state = NPlayerGameState::new(players(4), 4);
+        let mut validator = NPlayerRoundValidator::new(state).unwrap();
+
+        for round in 0..4 {
+            // Player 3 defects every other round
+            let player_3 = if round % 2 == 0 { Move::Defect } else { Move::Cooperate };
+            validator
+                .play_round(&[Move::Cooperate, Move::Cooperate, Move::Cooperate, player_3])
+                .unwrap();
+        }
+
+        let state = validator.get_state();
+        let rep = state.reputation("tb1qplayer0").unwrap();
+        assert_eq!(rep.reputation_score, 100);
+        // Four moves are too few for Trusted
+        assert_eq!(rep.tier, 1);
+
+        let rep = state.reputation("tb1qplayer3").unwrap();
+        assert_eq!(rep.total_moves, 4);
+        assert_eq!(rep.cooperative_moves, 2);
+        assert_eq!(rep.reputation_score, 50);
+
+        assert!(state.reputation("tb1qunknown").is_none());
+    }
+}
@@ -0,0 +1,1429 @@
+/**
+ * ZKVM PROVING INTERFACE
+ *
+ * Input and output types of the trust-game zkVM program, shared by the guest
+ * binary and by host-side tooling (transaction building, verification).
+ *
+ * Inside the zkVM, input and public values use the compact binary encoding
+ * from encoding.rs:
+ * - Input:         address, move count + bit-packed moves, opponent moves, [R, T, S, P],
+ *   optional opponent [R, T, S, P] for asymmetric games, further matches (each with an
+ *   opponent id and the same fields), then an optional previous proof (public values +
+ *   verifying key digest), signed game transcripts (see transcript.rs), then the
+ *   game policy
+ * - Public values: spell data (see spell.rs) followed by the 32-byte spell commitment
+ *
+ * Incremental proofs: instead of the full history, an input may carry the
+ * output of a previous proof. The guest verifies that proof with SP1 recursion
+ * and folds only the new session's moves into the cumulative reputation.
+ *
+ * Multiple matches: the reputation score averages cooperation per opponent, weighting
+ * each by its moves up to `MAX_OPPONENT_WEIGHT`, so one long match cannot dominate.
+ * The output carries the per-opponent breakdown, with the total payoff of each side
+ * replayed from its own payoff matrix.
+ *
+ * Countersigned play: matches from `transcripts` are signed by both players and
+ * verified in the guest. `countersigned` in the output tells verifiers whether ALL
+ * of the reputation (including previous sessions) comes from such transcripts.
+ * Each transcript counts once: the output lists the sorted ids of every game
+ * folded in so far, and a game already listed (or repeated in the input) is
+ * rejected. Governance only gives voting power to countersigned reputations.
+ *
+ * Game policy: payoff matrices must classify as a game the input's `GamePolicy`
+ * allows (Prisoner's Dilemma only by default). The output commits to the policy,
+ * so verifiers can tell what kind of games a reputation was earned in.
+ *
+ * Sybil resistance: `SybilPolicy::default()` limits matches per opponent, caps the
+ * tier by the number of distinct opponents and flags mirrored matches; every
+ * heuristic that fires is listed in `sybil_flags` (see sybil.rs).
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::encoding::{to_hex, CanonicalEncode, Decoder, Encoder};
+use crate::spell::{encode_spell, spell_commitment, SPELL_VERSION};
+use crate::sybil::{SybilFlag, SybilPolicy};
+use crate::transcript::GameTranscript;
+use crate::{
+    AsymmetricPayoffMatrix, GamePolicy, MoveHistory, PayoffMatrix, PlayerReputation,
+    ReputationConfig,
+};
+
+/// Input to the zkVM: game history to prove
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveInput {
+    /// Player's Bitcoin address
+    pub player_address: String,
+    /// Sequence of moves made by the player
+    pub moves: MoveHistory,
+    /// Opponent's moves (for context)
+    pub opponent_moves: MoveHistory,
+    /// Payoff matrix [R, T, S, P]
+    pub payoffs: [i32; 4],
+    /// Opponent's payoff matrix [R, T, S, P] in an asymmetric game
+    /// (None when both players share `payoffs`)
+    #[serde(default)]
+    pub opponent_payoffs: Option<[i32; 4]>,
+    /// Further matches against named opponents
+    /// (the top-level moves, if any, count as a match against the unnamed opponent "")
+    #[serde(default)]
+    pub matches: Vec<MatchHistory>,
+    /// Previously proven reputation to build on (None for a first session)
+    #[serde(default)]
+    pub previous: Option<PreviousProof>,
+    /// Matches signed by both players, verified in the guest
+    #[serde(default)]
+    pub transcripts: Vec<GameTranscript>,
+    /// Game classes the payoff matrices may define
+    #[serde(default)]
+    pub game_policy: GamePolicy,
+}
+
+/// One match against a single opponent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchHistory {
+    /// Opponent identifier (e.g. their Bitcoin address)
+    pub opponent: String,
+    /// Player's moves in this match
+    pub moves: MoveHistory,
+    /// Opponent's moves in this match
+    pub opponent_moves: MoveHistory,
+    /// Payoff matrix [R, T, S, P]
+    pub payoffs: [i32; 4],
+    /// Opponent's payoff matrix [R, T, S, P] in an asymmetric game
+    #[serde(default)]
+    pub opponent_payoffs: Option<[i32; 4]>,
+}
+
+impl MatchHistory {
+    /// Payoff matrices for the player (player 1) and opponent (player 2)
+    pub fn payoff_matrix(&self, policy: &GamePolicy) -> Result<AsymmetricPayoffMatrix, String> {
+        payoff_matrix(self.payoffs, self.opponent_payoffs, policy)
+    }
+}
+
+/// A player's record against one opponent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpponentRecord {
+    /// Opponent identifier ("" for the top-level moves of `ProveInput`)
+    pub opponent: String,
+    /// Moves played against this opponent
+    pub total_moves: u32,
+    /// Cooperative moves against this opponent
+    pub cooperative_moves: u32,
+    /// Matches counted against this opponent
+    #[serde(default)]
+    pub matches: u32,
+    /// Player's total payoff against this opponent
+    #[serde(default)]
+    pub payoff: i64,
+    /// Opponent's total payoff, from their own payoff matrix
+    #[serde(default)]
+    pub opponent_payoff: i64,
+    /// Matches counted from transcripts signed by this opponent
+    #[serde(default)]
+    pub countersigned_matches: u32,
+}
+
+/// A previously proven reputation, verified inside the guest via SP1 recursion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviousProof {
+    /// Output committed by the previous proof
+    pub output: ProveOutput,
+    /// Verifying key digest of the program that produced the previous proof
+    pub vkey_digest: [u32; 8],
+}
+
+/// Output from the zkVM: verified reputation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveOutput {
+    /// Player's address
+    pub player_address: String,
+    /// Total moves
+    pub total_moves: u32,
+    /// Cooperative moves
+    pub cooperative_moves: u32,
+    /// Calculated reputation score (0-100)
+    pub reputation_score: u32,
+    /// Reputation tier (0=Suspicious, 1=Neutral, 2=Trusted)
+    pub tier: u8,
+    /// Voting power
+    pub voting_power: u32,
+    /// Number of game sessions folded into this reputation
+    #[serde(default)]
+    pub sessions: u32,
+    /// Verifying key digest the previous proof was checked against
+    /// (verifiers must check it is the trust-game program's own key)
+    #[serde(default)]
+    pub recursion_vkey: Option<[u32; 8]>,
+    /// Per-opponent breakdown, sorted by opponent
+    #[serde(default)]
+    pub opponents: Vec<OpponentRecord>,
+    /// Whether every move behind this reputation is signed by both players
+    #[serde(default)]
+    pub countersigned: bool,
+    /// Reputation-farming heuristics that fired, with reasons
+    #[serde(default)]
+    pub sybil_flags: Vec<SybilFlag>,
+    /// Game classes the reputation was earned in (copied from the input)
+    #[serde(default)]
+    pub game_policy: GamePolicy,
+    /// Sorted ids of the transcripts counted so far (see `GameTranscript::game_id`)
+    #[serde(default)]
+    pub game_ids: Vec<[u8; 32]>,
+    /// Hex spell commitment over the fields above (see `spell::spell_commitment`)
+    #[serde(default)]
+    pub spell_commitment: String,
+}
+
+/// Moves, opponent moves, payoffs and optional opponent payoffs of one match
+fn encode_match(
+    encoder: &mut Encoder,
+    moves: &MoveHistory,
+    opponent_moves: &MoveHistory,
+    payoffs: &[i32; 4],
+    opponent_payoffs: &Option<[i32; 4]>,
+) {
+    for moves in [moves, opponent_moves] {
+        encoder.u32(moves.len());
+        for byte in moves.packed() {
+            encoder.u8(*byte);
+        }
+    }
+    for payoff in payoffs {
+        encoder.i32(*payoff);
+    }
+    match opponent_payoffs {
+        Some(opponent_payoffs) => {
+            encoder.u8(1);
+            for payoff in opponent_payoffs {
+                encoder.i32(*payoff);
+            }
+        }
+        None => {
+            encoder.u8(0);
+        }
+    }
+}
+
+type DecodedMatch = (MoveHistory, MoveHistory, [i32; 4], Option<[i32; 4]>);
+
+/// Inverse of `encode_match`
+fn decode_match(decoder: &mut Decoder) -> Result<DecodedMatch, String> {
+    let decode_moves = |decoder: &mut Decoder| -> Result<MoveHistory, String> {
+        let count = decoder.u32()?;
+        let packed = decoder.take(MoveHistory::packed_len(count))?;
+        MoveHistory::from_packed(count, packed.to_vec())
+    };
+    let moves = decode_moves(decoder)?;
+    let opponent_moves = decode_moves(decoder)?;
+
+    let decode_payoffs = |decoder: &mut Decoder| -> Result<[i32; 4], String> {
+        let mut payoffs = [0i32; 4];
+        for payoff in payoffs.iter_mut() {
+            *payoff = decoder.i32()?;
+        }
+        Ok(payoffs)
+    };
+    let payoffs = decode_payoffs(decoder)?;
+    let opponent_payoffs = match decoder.u8()? {
+        0 => None,
+        1 => Some(decode_payoffs(decoder)?),
+        other => return Err(format!("Invalid option tag {}", other)),
+    };
+
+    Ok((moves, opponent_moves, payoffs, opponent_payoffs))
+}
+
+impl CanonicalEncode for MatchHistory {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.opponent);
+        encode_match(
+            encoder,
+            &self.moves,
+            &self.opponent_moves,
+            &self.payoffs,
+            &self.opponent_payoffs,
+        );
+    }
+}
+
+impl CanonicalEncode for ProveInput {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.player_address);
+        encode_match(
+            encoder,
+            &self.moves,
+            &self.opponent_moves,
+            &self.payoffs,
+            &self.opponent_payoffs,
+        );
+        let matches: Vec<&MatchHistory> = self.matches.iter().collect();
+        encoder.seq(&matches);
+        match &self.previous {
+            Some(previous) => {
+                encoder.u8(1).bytes(&previous.output.to_public_values());
+                for word in &previous.vkey_digest {
+                    encoder.u32(*word);
+                }
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+        let transcripts: Vec<&GameTranscript> = self.transcripts.iter().collect();
+        encoder.seq(&transcripts);
+        self.game_policy.encode(encoder);
+    }
+}
+
+impl ProveInput {
+    /// Decode the compact binary input read by the zkVM guest
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut decoder = Decoder::new(bytes);
+        let player_address = decoder.str()?;
+        let (moves, opponent_moves, payoffs, opponent_payoffs) = decode_match(&mut decoder)?;
+
+        let match_count = decoder.u32()?;
+        let mut matches = Vec::new();
+        for _ in 0..match_count {
+            let opponent = decoder.str()?;
+            let (moves, opponent_moves, payoffs, opponent_payoffs) = decode_match(&mut decoder)?;
+            matches.push(MatchHistory {
+                opponent,
+                moves,
+                opponent_moves,
+                payoffs,
+                opponent_payoffs,
+            });
+        }
+
+        let previous = match decoder.u8()? {
+            0 => None,
+            1 => {
+                let output = ProveOutput::from_public_values(decoder.bytes()?)?;
+                let mut vkey_digest = [0u32; 8];
+                for word in vkey_digest.iter_mut() {
+                    *word = decoder.u32()?;
+                }
+                Some(PreviousProof {
+                    output,
+                    vkey_digest,
+                })
+            }
+            other => return Err(format!("Invalid option tag {}", other)),
+        };
+        let mut transcripts = Vec::new();
+        for _ in 0..decoder.u32()? {
+            transcripts.push(GameTranscript::decode(&mut decoder)?);
+        }
+        let game_policy = GamePolicy::decode(&mut decoder)?;
+        decoder.finish()?;
+
+        Ok(ProveInput {
+            player_address,
+            moves,
+            opponent_moves,
+            payoffs,
+            opponent_payoffs,
+            matches,
+            previous,
+            transcripts,
+            game_policy,
+        })
+    }
+
+    /// Whether every move in this input, and in any previous proof, is countersigned
+    pub fn is_countersigned(&self) -> bool {
+        let previous_countersigned = match &self.previous {
+            Some(previous) => previous.output.countersigned,
+            None => true,
+        };
+        previous_countersigned && self.moves.is_empty() && self.matches.is_empty()
+    }
+
+    /// Payoff matrices for the player (player 1) and opponent (player 2)
+    pub fn payoff_matrix(&self) -> Result<AsymmetricPayoffMatrix, String> {
+        payoff_matrix(self.payoffs, self.opponent_payoffs, &self.game_policy)
+    }
+}
+
+/// Payoff matrix from `[R, T, S, P]`, checked against the game policy
+fn checked_matrix([r, t, s, p]: [i32; 4], policy: &GamePolicy) -> Result<PayoffMatrix, String> {
+    let matrix = PayoffMatrix { r, s, t, p };
+    policy.check(&matrix)?;
+    Ok(matrix)
+}
+
+/// Validated payoff matrices from `[R, T, S, P]` arrays
+fn payoff_matrix(
+    payoffs: [i32; 4],
+    opponent_payoffs: Option<[i32; 4]>,
+    policy: &GamePolicy,
+) -> Result<AsymmetricPayoffMatrix, String> {
+    let player = checked_matrix(payoffs, policy)?;
+    match opponent_payoffs {
+        Some(opponent_payoffs) => Ok(AsymmetricPayoffMatrix {
+            player_1: player,
+            player_2: checked_matrix(opponent_payoffs, policy)
+                .map_err(|e| format!("Opponent payoffs: {}", e))?,
+        }),
+        None => Ok(AsymmetricPayoffMatrix::symmetric(player)),
+    }
+}
+
+/// Replay a match, returning the record of the player's side
+/// Each side's payoff comes from its own matrix, so asymmetric games are scored correctly.
+fn replay(
+    opponent: String,
+    moves: &MoveHistory,
+    opponent_moves: &MoveHistory,
+    payoffs: &AsymmetricPayoffMatrix,
+) -> Result<OpponentRecord, String> {
+    if moves.len() != opponent_moves.len() {
+        return Err(format!(
+            "{} moves but {} opponent moves",
+            moves.len(),
+            opponent_moves.len()
+        ));
+    }
+
+    let (mut payoff, mut opponent_payoff) = (0i64, 0i64);
+    for (move_1, move_2) in moves.iter().zip(opponent_moves.iter()) {
+        let (payoff_1, payoff_2) = payoffs.payoffs(move_1, move_2);
+        payoff += payoff_1 as i64;
+        opponent_payoff += payoff_2 as i64;
+    }
+
+    Ok(OpponentRecord {
+        opponent,
+        total_moves: moves.len(),
+        cooperative_moves: moves.cooperations(),
+        matches: 1,
+        payoff,
+        opponent_payoff,
+        countersigned_matches: 0,
+    })
+}
+
+impl CanonicalEncode for OpponentRecord {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder
+            .str(&self.opponent)
+            .u32(self.total_moves)
+            .u32(self.cooperative_moves)
+            .u32(self.matches)
+            .i64(self.payoff)
+            .i64(self.opponent_payoff)
+            .u32(self.countersigned_matches);
+    }
+}
+
+// `spell_commitment` is derived from this encoding, so it is not part of it
+impl CanonicalEncode for ProveOutput {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder
+            .str(&self.player_address)
+            .u32(self.total_moves)
+            .u32(self.cooperative_moves)
+            .u32(self.reputation_score)
+            .u8(self.tier)
+            .u32(self.voting_power)
+            .u32(self.sessions);
+        match &self.recursion_vkey {
+            Some(vkey_digest) => {
+                encoder.u8(1);
+                for word in vkey_digest {
+                    encoder.u32(*word);
+                }
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+        let opponents: Vec<&OpponentRecord> = self.opponents.iter().collect();
+        encoder.seq(&opponents);
+        encoder.bool(self.countersigned);
+        let sybil_flags: Vec<&SybilFlag> = self.sybil_flags.iter().collect();
+        encoder.seq(&sybil_flags);
+        self.game_policy.encode(encoder);
+        encoder.u32(self.game_ids.len() as u32);
+        for game_id in &self.game_ids {
+            for byte in game_id {
+                encoder.u8(*byte);
+            }
+        }
+    }
+}
+
+impl ProveOutput {
+    /// Voting power this reputation carries in governance: none unless every move
+    /// behind it is countersigned, since unsigned moves could be invented by the prover
+    pub fn governance_voting_power(&self) -> u32 {
+        if self.countersigned {
+            self.voting_power
+        } else {
+            0
+        }
+    }
+
+    /// Check every session folded into this proof was verified against `program_vkey`,
+    /// the trust-game program's own key (the guest cannot know its own key)
+    pub fn check_recursion_vkey(&self, program_vkey: &[u32; 8]) -> Result<(), String> {
+        match self.recursion_vkey {
+            Some(recursion_vkey) if recursion_vkey != *program_vkey => {
+                Err("Proof chains a session verified against a different program".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Public values committed by the zkVM guest: spell data then spell commitment
+    pub fn to_public_values(&self) -> Vec<u8> {
+        let mut bytes = encode_spell(self);
+        bytes.extend_from_slice(&spell_commitment(self));
+        bytes
+    }
+
+    /// Decode zkVM public values, checking the embedded spell commitment
+    pub fn from_public_values(bytes: &[u8]) -> Result<Self, String> {
+        let mut decoder = Decoder::new(bytes);
+
+        let version = decoder.u8()?;
+        if version != SPELL_VERSION {
+            return Err(format!("Unsupported spell version {}", version));
+        }
+
+        let mut output = ProveOutput {
+            player_address: decoder.str()?,
+            total_moves: decoder.u32()?,
+            cooperative_moves: decoder.u32()?,
+            reputation_score: decoder.u32()?,
+            tier: decoder.u8()?,
+            voting_power: decoder.u32()?,
+            sessions: decoder.u32()?,
+            recursion_vkey: match decoder.u8()? {
+                0 => None,
+                1 => {
+                    let mut vkey_digest = [0u32; 8];
+                    for word in vkey_digest.iter_mut() {
+                        *word = decoder.u32()?;
+                    }
+                    Some(vkey_digest)
+                }
+                other => return Err(format!("Invalid option tag {}", other)),
+            },
+            opponents: Vec::new(),
+            countersigned: false,
+            sybil_flags: Vec::new(),
+            game_policy: GamePolicy::default(),
+            game_ids: Vec::new(),
+            spell_commitment: String::new(),
+        };
+        for _ in 0..decoder.u32()? {
+            output.opponents.push(OpponentRecord {
+                opponent: decoder.str()?,
+                total_moves: decoder.u32()?,
+                cooperative_moves: decoder.u32()?,
+                matches: decoder.u32()?,
+                payoff: decoder.i64()?,
+                opponent_payoff: decoder.i64()?,
+                countersigned_matches: decoder.u32()?,
+            });
+        }
+        output.countersigned = decoder.bool()?;
+        for _ in 0..decoder.u32()? {
+            output.sybil_flags.push(SybilFlag::decode(&mut decoder)?);
+        }
+        output.game_policy = GamePolicy::decode(&mut decoder)?;
+        for _ in 0..decoder.u32()? {
+            output.game_ids.push(decoder.hash()?);
+        }
+        let commitment = decoder.hash()?;
+        decoder.finish()?;
+
+        if commitment != spell_commitment(&output) {
+            return Err("Spell commitment does not match public values".to_string());
+        }
+        output.spell_commitment = to_hex(&commitment);
+
+        Ok(output)
+    }
+}
+
+/// zkVM phases reported by the guest's cycle tracker, in execution order
+pub const CYCLE_PHASES: [&str; 5] = [
+    "deserialization",
+    "previous_proof",
+    "move_validation",
+    "reputation",
+    "output",
+];
+
+/// Validate moves and payoff matrices, returning this session's record per match
+/// (moves are valid by construction of `MoveHistory`, so only defections are counted)
+pub fn validate_history(input: &ProveInput) -> Result<Vec<OpponentRecord>, String> {
+    // Validate payoff matrices (each player's game must be allowed by the policy)
+    let payoffs = input.payoff_matrix()?;
+
+    let mut records = Vec::new();
+    if !input.moves.is_empty() {
+        records.push(replay(
+            String::new(),
+            &input.moves,
+            &input.opponent_moves,
+            &payoffs,
+        )?);
+    }
+
+    let replay_match = |match_history: MatchHistory| {
+        let payoffs = match_history.payoff_matrix(&input.game_policy)?;
+        replay(
+            match_history.opponent,
+            &match_history.moves,
+            &match_history.opponent_moves,
+            &payoffs,
+        )
+    };
+    for (i, match_history) in input.matches.iter().enumerate() {
+        let record =
+            replay_match(match_history.clone()).map_err(|e| format!("Match {}: {}", i, e))?;
+        records.push(record);
+    }
+
+    // Signed transcripts: both players' signatures are checked here, inside the guest,
+    // with both addresses on the player's network, and each game counts once across
+    // this input and previous sessions
+    let mut game_ids = previous_game_ids(input);
+    for (i, transcript) in input.transcripts.iter().enumerate() {
+        if !game_ids.insert(transcript.game_id()) {
+            return Err(format!("Transcript {}: game already counted", i));
+        }
+        let mut record = transcript
+            .match_for(&input.player_address)
+            .and_then(replay_match)
+            .map_err(|e| format!("Transcript {}: {}", i, e))?;
+        record.countersigned_matches = 1;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Ids of the transcripts counted in the previous proof, if any
+fn previous_game_ids(input: &ProveInput) -> BTreeSet<[u8; 32]> {
+    match &input.previous {
+        Some(previous) => previous.output.game_ids.iter().copied().collect(),
+        None => BTreeSet::new(),
+    }
+}
+
+/// Check that a previous proof can be extended by this input
+/// (the proof itself is verified by the guest, see main.rs)
+pub fn validate_previous(input: &ProveInput) -> Result<(), String> {
+    let previous = match &input.previous {
+        Some(previous) => previous,
+        None => return Ok(()),
+    };
+
+    if previous.output.player_address != input.player_address {
+        return Err("Previous proof belongs to a different player".to_string());
+    }
+
+    if previous.output.game_policy.canonical_bytes() != input.game_policy.canonical_bytes() {
+        return Err("Previous proof was made under a different game policy".to_string());
+    }
+
+    // Every link in the chain must have been verified against the same program
+    if let Some(vkey_digest) = previous.output.recursion_vkey {
+        if vkey_digest != previous.vkey_digest {
+            return Err("Previous proof was verified against a different program".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Sybil flags for mirrored matches in this session
+fn mirrored_flags(input: &ProveInput, policy: &SybilPolicy) -> Vec<SybilFlag> {
+    let mut sides = vec![(String::new(), input.moves.clone(), input.opponent_moves.clone())];
+    for match_history in &input.matches {
+        sides.push((
+            match_history.opponent.clone(),
+            match_history.moves.clone(),
+            match_history.opponent_moves.clone(),
+        ));
+    }
+    for transcript in &input.transcripts {
+        let opponent = if transcript.player_1.address == input.player_address {
+            &transcript.player_2
+        } else {
+            &transcript.player_1
+        };
+        sides.push((
+            to_hex(&opponent.public_key),
+            transcript.rounds.iter().map(|round| round.move_1).collect(),
+            transcript.rounds.iter().map(|round| round.move_2).collect(),
+        ));
+    }
+
+    sides
+        .into_iter()
+        .filter(|(_, moves, opponent_moves)| policy.is_mirrored(moves, opponent_moves))
+        .map(|(opponent, moves, _)| SybilFlag::MirroredHistory {
+            opponent,
+            moves: moves.len(),
+        })
+        .collect()
+}
+
+/// Calculate reputation for a validated history and commit to the spell
+/// With a previous proof, the new session is folded into the cumulative per-opponent totals
+pub fn reputation_output(
+    input: &ProveInput,
+    records: Vec<OpponentRecord>,
+) -> Result<ProveOutput, String> {
+    let policy = SybilPolicy::default();
+    let (previous_opponents, previous_flags, previous_sessions, recursion_vkey) =
+        match &input.previous {
+            Some(previous) => (
+                previous.output.opponents.as_slice(),
+                previous.output.sybil_flags.as_slice(),
+                previous.output.sessions,
+                Some(previous.vkey_digest),
+            ),
+            None => (&[][..], &[][..], 0, None),
+        };
+
+    // Merge records for the same opponent, across matches and sessions, ignoring
+    // matches beyond the per-opponent limit. Opponents of unsigned matches are
+    // labels chosen by the prover, so those matches all share one limit.
+    let mut totals: BTreeMap<String, OpponentRecord> = BTreeMap::new();
+    let mut unsigned_matches = 0u32;
+    for record in previous_opponents {
+        totals.insert(record.opponent.clone(), record.clone());
+        unsigned_matches += record.matches.saturating_sub(record.countersigned_matches);
+    }
+    let mut ignored: BTreeMap<String, u32> = BTreeMap::new();
+    for record in records {
+        let counted = if record.countersigned_matches > 0 {
+            totals
+                .get(&record.opponent)
+                .map_or(0, |entry| entry.countersigned_matches)
+        } else {
+            unsigned_matches
+        };
+        if counted >= policy.max_matches_per_opponent {
+            *ignored.entry(record.opponent).or_insert(0) += 1;
+            continue;
+        }
+        unsigned_matches += record.matches - record.countersigned_matches;
+
+        let entry = totals.entry(record.opponent.clone()).or_insert(OpponentRecord {
+            opponent: record.opponent.clone(),
+            total_moves: 0,
+            cooperative_moves: 0,
+            matches: 0,
+            payoff: 0,
+            opponent_payoff: 0,
+            countersigned_matches: 0,
+        });
+        entry.countersigned_matches += record.countersigned_matches;
+        entry.total_moves = entry
+            .total_moves
+            .checked_add(record.total_moves)
+            .ok_or("Total moves overflow".to_string())?;
+        entry.cooperative_moves += record.cooperative_moves;
+        entry.matches += record.matches;
+        entry.payoff = entry
+            .payoff
+            .checked_add(record.payoff)
+            .ok_or("Payoff overflow".to_string())?;
+        entry.opponent_payoff = entry
+            .opponent_payoff
+            .checked_add(record.opponent_payoff)
+            .ok_or("Payoff overflow".to_string())?;
+    }
+
+    // Calculate cumulative reputation, weighting opponents by moves up to a cap, under
+    // the protocol's activity minimums (`ReputationConfig::default()`)
+    let counts: Vec<(u32, u32)> = totals
+        .values()
+        .map(|record| (record.total_moves, record.cooperative_moves))
+        .collect();
+    counts
+        .iter()
+        .try_fold(0u32, |total, (moves, _)| total.checked_add(*moves))
+        .ok_or("Total moves overflow".to_string())?;
+    let games = totals.values().map(|record| record.matches).sum();
+    let mut reputation = PlayerReputation::calculate_with_config(
+        input.player_address.clone(),
+        &counts,
+        games,
+        &ReputationConfig::default(),
+    );
+
+    // Earlier flags still apply; the opponent count is re-checked below
+    let mut sybil_flags: Vec<SybilFlag> = previous_flags
+        .iter()
+        .filter(|flag| !matches!(flag, SybilFlag::TooFewOpponents { .. }))
+        .cloned()
+        .collect();
+    for (opponent, ignored) in ignored {
+        sybil_flags.push(SybilFlag::ExcessMatches { opponent, ignored });
+    }
+    sybil_flags.extend(mirrored_flags(input, &policy));
+
+    // Only opponents who signed a transcript are known to be distinct players
+    // (their records are keyed by signing key, see `GameTranscript::match_for`)
+    let distinct_opponents = totals
+        .values()
+        .filter(|record| record.countersigned_matches > 0)
+        .count() as u32;
+    if reputation.cap_tier(policy.max_tier(distinct_opponents)) {
+        sybil_flags.push(SybilFlag::TooFewOpponents {
+            opponents: distinct_opponents,
+            tier: reputation.tier,
+        });
+    }
+
+    let mut game_ids = previous_game_ids(input);
+    game_ids.extend(input.transcripts.iter().map(GameTranscript::game_id));
+
+    // Create output, committing to the spell so verifiers can match the on-chain OP_RETURN
+    let mut output = ProveOutput {
+        player_address: input.player_address.clone(),
+        total_moves: reputation.total_moves,
+        cooperative_moves: reputation.cooperative_moves,
+        reputation_score: reputation.reputation_score,
+        tier: reputation.tier,
+        voting_power: reputation.voting_power,
+        sessions: previous_sessions
+            .checked_add(1)
+            .ok_or("Session count overflow".to_string())?,
+        recursion_vkey,
+        opponents: totals.into_values().collect(),
+        countersigned: input.is_countersigned(),
+        sybil_flags,
+        game_policy: input.game_policy.clone(),
+        game_ids: game_ids.into_iter().collect(),
+        spell_commitment: String::new(),
+    };
+    output.spell_commitment = to_hex(&spell_commitment(&output));
+
+    Ok(output)
+}
+
+/// Validate a game history and compute the proven reputation
+/// This is the whole zkVM program; the entrypoint only adds I/O, cycle tracking
+/// and verification of the previous proof
+pub fn prove(input: &ProveInput) -> Result<ProveOutput, String> {
+    validate_previous(input)?;
+    let records = validate_history(input)?;
+    reputation_output(input, records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameClass, Move};
+
+    fn sample_input() -> ProveInput {
+        ProveInput {
+            player_address: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+            moves: MoveHistory::from_values(&[0, 0, 1, 0, 1]).unwrap(),
+            opponent_moves: MoveHistory::from_values(&[0, 1, 1, 0, 0]).unwrap(),
+            payoffs: [2, 3, -1, 0],
+            opponent_payoffs: None,
+            matches: Vec::new(),
+            previous: None,
+            transcripts: Vec::new(),
+            game_policy: GamePolicy::default(),
+        }
+    }
+
+    #[test]
+    fn test_prove_sample_input() {
+        let output = prove(&sample_input()).unwrap();
+
+        assert_eq!(output.total_moves, 5);
+        assert_eq!(output.cooperative_moves, 3);
+        assert_eq!(output.reputation_score, 60);
+        assert_eq!(output.tier, 1);
+        assert_eq!(output.voting_power, 60);
+        assert_eq!(
+            output.spell_commitment,
+            "1e66d03473ab40cf876a068653fe262fa0dcea09db34ede3bdd30c43df9b0e73"
+        );
+    }
+
+    #[test]
+    fn test_prove_rejects_invalid_input() {
+        // Moves other than 0/1 are rejected when the input is read
+        let json = r#"{"player_address": "tb1q", "moves": [0, 2], "opponent_moves": [0, 0],
+                       "payoffs": [2, 3, -1, 0]}"#;
+        assert!(serde_json::from_str::<ProveInput>(json).is_err());
+
+        // T <= R is not a Prisoner's Dilemma
+        let mut input = sample_input();
+        input.payoffs = [3, 3, -1, 0];
+        assert!(prove(&input).is_err());
+
+        // The same holds for the opponent's matrix in an asymmetric game
+        let mut input = sample_input();
+        input.opponent_payoffs = Some([3, 3, -1, 0]);
+        assert_eq!(
+            prove(&input).unwrap_err(),
+            "Opponent payoffs: Payoff matrix does not define a recognized 2x2 game"
+        );
+    }
+
+    #[test]
+    fn test_game_policy() {
+        // R > T: a Stag Hunt, rejected by the default policy
+        let stag_hunt = ProveInput {
+            payoffs: [4, 3, -1, 0],
+            ..sample_input()
+        };
+        assert_eq!(
+            prove(&stag_hunt).unwrap_err(),
+            "Stag Hunt games are not allowed by this deployment"
+        );
+
+        // Accepted when the input's policy allows it, and the output says so
+        let policy = GamePolicy::new(vec![GameClass::StagHunt, GameClass::PrisonersDilemma]);
+        let input = ProveInput {
+            game_policy: policy.clone(),
+            ..stag_hunt
+        };
+        let decoded = ProveInput::decode(&input.canonical_bytes()).unwrap();
+        let output = prove(&decoded).unwrap();
+        assert_eq!(output.reputation_score, 60);
+        let public_values = output.to_public_values();
+        let committed = ProveOutput::from_public_values(&public_values).unwrap();
+        assert_eq!(committed.game_policy.canonical_bytes(), policy.canonical_bytes());
+
+        // A chain cannot switch policies midway
+        let switched = ProveInput {
+            previous: Some(PreviousProof {
+                output,
+                vkey_digest: [7u32; 8],
+            }),
+            ..sample_input()
+        };
+        assert_eq!(
+            prove(&switched).unwrap_err(),
+            "Previous proof was made under a different game policy"
+        );
+    }
+
+    #[test]
+    fn test_input_binary_roundtrip() {
+        let input = sample_input();
+        let bytes = input.canonical_bytes();
+
+        // One bit per move instead of a JSON number
+        assert_eq!(bytes.len(), 4 + 42 + 4 + 1 + 4 + 1 + 16 + 1 + 4 + 1 + 4 + 4 + 1);
+
+        let decoded = ProveInput::decode(&bytes).unwrap();
+        assert_eq!(decoded.player_address, input.player_address);
+        assert_eq!(decoded.moves, input.moves);
+        assert_eq!(decoded.opponent_moves, input.opponent_moves);
+        assert_eq!(decoded.payoffs, input.payoffs);
+        assert!(decoded.opponent_payoffs.is_none());
+        assert!(decoded.previous.is_none());
+
+        assert!(ProveInput::decode(&bytes[..bytes.len() - 1]).is_err());
+
+        // Long histories pack eight moves per byte
+        let long_input = ProveInput {
+            moves: (0..10_000)
+                .map(|i| if i % 5 == 4 { Move::Defect } else { Move::Cooperate })
+                .collect(),
+            ..sample_input()
+        };
+        let long_bytes = long_input.canonical_bytes();
+        assert_eq!(long_bytes.len(), bytes.len() - 1 + 1_250);
+        let decoded = ProveInput::decode(&long_bytes).unwrap();
+        assert_eq!(decoded.moves.cooperations(), 8_000);
+
+        // Asymmetric games carry the opponent's matrix
+        let asymmetric = ProveInput {
+            opponent_payoffs: Some([3, 4, -4, -1]),
+            ..sample_input()
+        };
+        let decoded = ProveInput::decode(&asymmetric.canonical_bytes()).unwrap();
+        assert_eq!(decoded.opponent_payoffs, Some([3, 4, -4, -1]));
+        assert!(!decoded.payoff_matrix().unwrap().is_symmetric());
+    }
+
+    #[test]
+    fn test_asymmetric_payoffs_scored() {
+        // Player: CCDCD against CDDCC, so each side gets its own matrix's payoffs
+        let symmetric = prove(&sample_input()).unwrap();
+        assert_eq!(symmetric.opponents[0].payoff, 6);
+        assert_eq!(symmetric.opponents[0].opponent_payoff, 6);
+
+        let asymmetric = ProveInput {
+            opponent_payoffs: Some([3, 4, -4, -1]),
+            ..sample_input()
+        };
+        let output = prove(&asymmetric).unwrap();
+        assert_eq!(output.opponents[0].payoff, 6);
+        assert_eq!(output.opponents[0].opponent_payoff, 5);
+        assert_eq!(
+            ProveOutput::from_public_values(&output.to_public_values()).unwrap(),
+            output
+        );
+
+        // Payoffs accumulate across sessions
+        let next = prove(&ProveInput {
+            previous: Some(PreviousProof {
+                output,
+                vkey_digest: [7u32; 8],
+            }),
+            ..asymmetric.clone()
+        })
+        .unwrap();
+        assert_eq!(next.opponents[0].payoff, 12);
+        assert_eq!(next.opponents[0].opponent_payoff, 10);
+
+        // A match cannot be replayed without every opponent move
+        let truncated = ProveInput {
+            opponent_moves: MoveHistory::from_values(&[0, 1]).unwrap(),
+            ..asymmetric
+        };
+        assert_eq!(prove(&truncated).unwrap_err(), "5 moves but 2 opponent moves");
+    }
+
+    fn sample_match(opponent: &str, moves: &[u32]) -> MatchHistory {
+        MatchHistory {
+            opponent: opponent.to_string(),
+            moves: MoveHistory::from_values(moves).unwrap(),
+            opponent_moves: MoveHistory::from_values(&vec![0; moves.len()]).unwrap(),
+            payoffs: [2, 3, -1, 0],
+            opponent_payoffs: None,
+        }
+    }
+
+    #[test]
+    fn test_matches_weighted_per_opponent() {
+        // A long cooperative match cannot hide defecting against everyone else
+        let input = ProveInput {
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            matches: vec![
+                sample_match("alice", &[0; 20]),
+                sample_match("bob", &[1, 1, 1, 1]),
+                sample_match("carol", &[1, 1, 0, 1]),
+            ],
+            ..sample_input()
+        };
+        let output = prove(&input).unwrap();
+
+        assert_eq!(output.total_moves, 28);
+        assert_eq!(output.cooperative_moves, 21);
+        // Alice's 20 moves weigh as 10: (10 * 100% + 4 * 0% + 4 * 25%) / 18,
+        // where pooling every move would give a Trusted 75%
+        assert_eq!(output.reputation_score, 61);
+        assert_eq!(output.tier, 1);
+
+        let opponents: Vec<(&str, u32, u32)> = output
+            .opponents
+            .iter()
+            .map(|r| (r.opponent.as_str(), r.total_moves, r.cooperative_moves))
+            .collect();
+        assert_eq!(opponents, vec![("alice", 20, 20), ("bob", 4, 0), ("carol", 4, 1)]);
+
+        // Breakdown survives the binary round trips
+        let decoded = ProveInput::decode(&input.canonical_bytes()).unwrap();
+        assert_eq!(decoded.matches.len(), 3);
+        assert_eq!(decoded.matches[2].opponent, "carol");
+        assert_eq!(
+            ProveOutput::from_public_values(&output.to_public_values()).unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn test_matches_merge_by_opponent() {
+        // Top-level moves plus a second match against the same named opponent
+        let input = ProveInput {
+            matches: vec![sample_match("alice", &[0, 0]), sample_match("alice", &[1, 0])],
+            ..sample_input()
+        };
+        let output = prove(&input).unwrap();
+
+        assert_eq!(output.opponents.len(), 2);
+        assert_eq!(output.opponents[0].opponent, "");
+        assert_eq!(output.opponents[1].total_moves, 4);
+        assert_eq!(output.opponents[1].cooperative_moves, 3);
+        // Mean of 60% and 75%, weighted by 5 and 4 moves
+        assert_eq!(output.reputation_score, 67);
+
+        // An invalid matrix in any match fails the proof
+        let mut invalid = input.clone();
+        invalid.matches[1].payoffs = [3, 3, -1, 0];
+        assert_eq!(
+            prove(&invalid).unwrap_err(),
+            "Match 1: Payoff matrix does not define a recognized 2x2 game"
+        );
+    }
+
+    #[test]
+    fn test_public_values_roundtrip() {
+        let output = prove(&sample_input()).unwrap();
+        let public_values = output.to_public_values();
+
+        assert_eq!(ProveOutput::from_public_values(&public_values).unwrap(), output);
+
+        // Tampering with the reputation breaks the embedded commitment
+        let mut tampered = public_values.clone();
+        let score_offset = 1 + 4 + 42 + 4 + 4 + 3;
+        tampered[score_offset] = 100;
+        assert!(ProveOutput::from_public_values(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_incremental_proof_folds_sessions() {
+        let vkey_digest = [7u32; 8];
+
+        // Session 1: 5 moves, 3 cooperative
+        let first = prove(&sample_input()).unwrap();
+        assert_eq!(first.sessions, 1);
+        assert_eq!(first.recursion_vkey, None);
+
+        // Session 2: only the new moves, building on session 1
+        let second_input = ProveInput {
+            moves: MoveHistory::from_values(&[0, 0, 0, 0, 0]).unwrap(),
+            opponent_moves: MoveHistory::from_values(&[0, 0, 0, 0, 0]).unwrap(),
+            previous: Some(PreviousProof {
+                output: first.clone(),
+                vkey_digest,
+            }),
+            ..sample_input()
+        };
+        let second = prove(&second_input).unwrap();
+
+        assert_eq!(second.total_moves, 10);
+        assert_eq!(second.cooperative_moves, 8);
+        assert_eq!(second.reputation_score, 80);
+        // Trusted needs three distinct countersigning opponents; unsigned play stays Neutral
+        assert_eq!(second.tier, 1);
+        assert_eq!(
+            second.sybil_flags,
+            vec![SybilFlag::TooFewOpponents { opponents: 0, tier: 1 }]
+        );
+        assert_eq!(second.sessions, 2);
+        assert_eq!(second.recursion_vkey, Some(vkey_digest));
+
+        // Same result as proving the full history at once
+        let full = prove(&ProveInput {
+            moves: MoveHistory::from_values(&[0, 0, 1, 0, 1, 0, 0, 0, 0, 0]).unwrap(),
+            opponent_moves: MoveHistory::from_values(&[0, 1, 1, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+            ..sample_input()
+        })
+        .unwrap();
+        assert_eq!(second.reputation_score, full.reputation_score);
+        assert_eq!(second.voting_power, full.voting_power);
+
+        // Binary encoding carries the previous proof
+        let decoded = ProveInput::decode(&second_input.canonical_bytes()).unwrap();
+        let previous = decoded.previous.unwrap();
+        assert_eq!(previous.output, first);
+        assert_eq!(previous.vkey_digest, vkey_digest);
+    }
+
+    #[test]
+    fn test_incremental_proof_rejects_mismatches() {
+        let first = prove(&sample_input()).unwrap();
+
+        // Another player's reputation cannot be extended
+        let other_player = ProveInput {
+            player_address: "tb1qother".to_string(),
+            previous: Some(PreviousProof {
+                output: first.clone(),
+                vkey_digest: [7u32; 8],
+            }),
+            ..sample_input()
+        };
+        assert!(prove(&other_player).is_err());
+
+        // A chain cannot switch programs midway
+        let second = prove(&ProveInput {
+            previous: Some(PreviousProof {
+                output: first,
+                vkey_digest: [7u32; 8],
+            }),
+            ..sample_input()
+        })
+        .unwrap();
+        let switched = ProveInput {
+            previous: Some(PreviousProof {
+                output: second,
+                vkey_digest: [8u32; 8],
+            }),
+            ..sample_input()
+        };
+        assert!(prove(&switched).is_err());
+    }
+
+    #[test]
+    fn test_incremental_proof_rejects_overflow() {
+        let mut first = prove(&sample_input()).unwrap();
+        let extend = |output: ProveOutput| ProveInput {
+            previous: Some(PreviousProof {
+                output,
+                vkey_digest: [7u32; 8],
+            }),
+            ..sample_input()
+        };
+
+        let mut saturated = first.clone();
+        saturated.opponents[0].total_moves = u32::MAX - 1;
+        assert_eq!(prove(&extend(saturated)).unwrap_err(), "Total moves overflow");
+
+        first.sessions = u32::MAX;
+        assert_eq!(prove(&extend(first)).unwrap_err(), "Session count overflow");
+    }
+
+    #[test]
+    fn test_countersigned_transcripts() {
+        use crate::transcript::tests::{address, signed_transcript};
+
+        let transcript = signed_transcript(&[
+            (Move::Cooperate, Move::Cooperate),
+            (Move::Defect, Move::Cooperate),
+        ]);
+        let input = ProveInput {
+            player_address: address(2),
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            transcripts: vec![transcript.clone()],
+            ..sample_input()
+        };
+
+        // Signatures survive the binary input and are checked by `prove`
+        let output = prove(&ProveInput::decode(&input.canonical_bytes()).unwrap()).unwrap();
+        assert_eq!(output.opponents[0].opponent, to_hex(&transcript.player_1.public_key));
+        assert_eq!(output.reputation_score, 100);
+        // Two moves are too few for Trusted under the protocol's minimums
+        assert_eq!(output.tier, 1);
+        assert!(output.countersigned);
+        assert_eq!(output.game_ids, vec![transcript.game_id()]);
+
+        // Unsigned moves alongside make the reputation no longer fully countersigned
+        let mixed = ProveInput {
+            player_address: address(2),
+            transcripts: vec![transcript.clone()],
+            ..sample_input()
+        };
+        assert!(!prove(&mixed).unwrap().countersigned);
+
+        // A transcript missing a counter-signature is rejected
+        let mut unsigned = transcript;
+        unsigned.rounds[0].signature_1 = None;
+        let forged = ProveInput {
+            transcripts: vec![unsigned],
+            ..input
+        };
+        assert_eq!(
+            prove(&forged).unwrap_err(),
+            "Transcript 0: Round 0: missing signature from player 1"
+        );
+    }
+
+    #[test]
+    fn test_transcripts_count_once() {
+        use crate::transcript::tests::{address, signed_game};
+
+        let transcript = signed_game(&[(Move::Cooperate, Move::Cooperate)], 1);
+        let rematch = signed_game(&[(Move::Cooperate, Move::Cooperate)], 2);
+        let input = ProveInput {
+            player_address: address(2),
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            transcripts: vec![transcript.clone()],
+            ..sample_input()
+        };
+
+        // The same game twice in one input
+        let doubled = ProveInput {
+            transcripts: vec![transcript.clone(), transcript.clone()],
+            ..input.clone()
+        };
+        assert_eq!(prove(&doubled).unwrap_err(), "Transcript 1: game already counted");
+
+        // A game counted in a previous session
+        let first = prove(&input).unwrap();
+        let extend = |transcript: GameTranscript| ProveInput {
+            previous: Some(PreviousProof {
+                output: first.clone(),
+                vkey_digest: [7u32; 8],
+            }),
+            transcripts: vec![transcript],
+            ..input.clone()
+        };
+        assert_eq!(
+            prove(&extend(transcript.clone())).unwrap_err(),
+            "Transcript 0: game already counted"
+        );
+
+        // A new game between the same players is folded in and remembered
+        let second = prove(&extend(rematch.clone())).unwrap();
+        let mut game_ids = vec![transcript.game_id(), rematch.game_id()];
+        game_ids.sort();
+        assert_eq!(second.game_ids, game_ids);
+        assert_eq!(
+            ProveOutput::from_public_values(&second.to_public_values()).unwrap(),
+            second
+        );
+    }
+
+    #[test]
+    fn test_opponents_identified_by_key() {
+        use crate::transcript::tests::{address, address_on, signed_between, signed_on};
+
+        let moves = [(Move::Cooperate, Move::Cooperate); 4];
+        let puppet = to_hex(&signed_between((1, 2), &moves, 0).player_2.public_key);
+        let input = |transcripts: Vec<GameTranscript>| ProveInput {
+            player_address: address(1),
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            transcripts,
+            ..sample_input()
+        };
+
+        // One sock-puppet key re-encoded for other networks is not another opponent
+        for hrp in ["bc", "bcrt"] {
+            let transcripts = vec![
+                signed_between((1, 2), &moves, 1),
+                signed_on(("tb", hrp), (1, 2), &moves, 2),
+            ];
+            assert_eq!(
+                prove(&input(transcripts)).unwrap_err(),
+                format!(
+                    "Transcript 1: {} is not the tb taproot address of its key",
+                    address_on(hrp, 2)
+                )
+            );
+        }
+
+        // Repeated games against one key share its record and its match limit
+        let output = prove(&input(
+            (0..12).map(|nonce| signed_between((1, 2), &moves, nonce)).collect(),
+        ))
+        .unwrap();
+        assert_eq!(output.opponents.len(), 1);
+        assert_eq!(output.opponents[0].opponent, puppet);
+        assert_eq!(output.opponents[0].countersigned_matches, 10);
+        assert_eq!(
+            output.sybil_flags,
+            vec![
+                SybilFlag::ExcessMatches { opponent: puppet, ignored: 2 },
+                SybilFlag::TooFewOpponents { opponents: 1, tier: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sybil_heuristics() {
+        use crate::transcript::tests::{address, signed_between};
+
+        let all_cooperate = || sample_match("sock-puppet", &[0; 12]);
+
+        // Farming one opponent: later matches ignored, mirrored matches flagged, tier capped
+        let farmed = ProveInput {
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            matches: (0..12).map(|_| all_cooperate()).collect(),
+            ..sample_input()
+        };
+        let output = prove(&farmed).unwrap();
+
+        assert_eq!(output.reputation_score, 100);
+        assert_eq!(output.tier, 1);
+        assert_eq!(output.voting_power, 100);
+        assert_eq!(output.opponents[0].matches, 10);
+        assert_eq!(output.total_moves, 120);
+        assert_eq!(output.sybil_flags.len(), 14);
+        assert_eq!(
+            output.sybil_flags[0].to_string(),
+            "Ignored 2 matches against \"sock-puppet\" beyond the per-opponent limit"
+        );
+        assert_eq!(
+            output.sybil_flags[13].to_string(),
+            "Tier capped at 1 with only 0 distinct countersigned opponents"
+        );
+        assert_eq!(
+            ProveOutput::from_public_values(&output.to_public_values()).unwrap(),
+            output
+        );
+
+        // The match limit holds across sessions
+        let next = prove(&ProveInput {
+            matches: vec![all_cooperate()],
+            previous: Some(PreviousProof {
+                output: output.clone(),
+                vkey_digest: [7u32; 8],
+            }),
+            ..farmed.clone()
+        })
+        .unwrap();
+        assert_eq!(next.opponents[0].matches, 10);
+        assert_eq!(next.total_moves, 120);
+
+        // Renaming the sock puppet for every match does not lift the limit
+        let renamed = ProveInput {
+            matches: (0..12)
+                .map(|i| sample_match(&format!("puppet-{:02}", i), &[0; 4]))
+                .collect(),
+            ..farmed.clone()
+        };
+        let output = prove(&renamed).unwrap();
+        assert_eq!(output.opponents.len(), 10);
+        assert_eq!(
+            output.sybil_flags[0],
+            SybilFlag::ExcessMatches {
+                opponent: "puppet-10".to_string(),
+                ignored: 1
+            }
+        );
+        assert_eq!(output.tier, 1);
+
+        // Unsigned matches against three named opponents do not reach Trusted
+        let varied = |opponent: &str| sample_match(opponent, &[0, 0, 0, 1]);
+        let unsigned = ProveInput {
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            matches: vec![varied("alice"), varied("bob"), varied("carol")],
+            ..sample_input()
+        };
+        let output = prove(&unsigned).unwrap();
+        assert_eq!(output.tier, 1);
+        assert_eq!(
+            output.sybil_flags,
+            vec![SybilFlag::TooFewOpponents { opponents: 0, tier: 1 }]
+        );
+
+        // Three distinct countersigning opponents reach Trusted, without flags
+        let moves = [
+            (Move::Cooperate, Move::Cooperate),
+            (Move::Cooperate, Move::Cooperate),
+            (Move::Cooperate, Move::Cooperate),
+            (Move::Defect, Move::Cooperate),
+        ];
+        let honest = ProveInput {
+            player_address: address(1),
+            moves: MoveHistory::new(),
+            opponent_moves: MoveHistory::new(),
+            transcripts: (2..=4).map(|seed| signed_between((1, seed), &moves, 1)).collect(),
+            ..sample_input()
+        };
+        let output = prove(&honest).unwrap();
+        assert_eq!(output.tier, 2);
+        assert!(output.sybil_flags.is_empty());
+        assert!(output.opponents.iter().all(|record| record.countersigned_matches == 1));
+    }
+}
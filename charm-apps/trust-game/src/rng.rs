@@ -0,0 +1,206 @@
+/**
+ * SEEDED RANDOMNESS
+ *
+ * Deterministic pseudo-random numbers for game mechanics (noise, the Random
+ * strategy, evolution, uncertain game length). The same seed always yields the
+ * same sequence, natively and inside the zkVM, so random outcomes can be
+ * reproduced and proven.
+ *
+ * The generator is the ChaCha20 keystream (RFC 8439) keyed by a 32-byte seed,
+ * implemented in-crate so it runs unchanged in the zkVM. Seeds should come from
+ * a public commitment that players cannot choose or predict before committing
+ * their moves, such as a Bitcoin block hash:
+ *
+ *   seed = SHA-256("trust-game/rng/v1" || purpose || commitment)
+ *
+ * `purpose` separates independent streams (e.g. "continuation", "noise") that
+ * are derived from the same commitment.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::{tagged_hash, Encoder};
+
+/// Domain tag for seeds derived from a public commitment
+const SEED_TAG: &str = "trust-game/rng/v1";
+
+/// "expand 32-byte k"
+const CHACHA_CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// ChaCha20 block function (RFC 8439 section 2.3), as 16 little-endian words
+pub fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &[u8; 12]) -> [u32; 16] {
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    let mut initial = [0u32; 16];
+    initial[..4].copy_from_slice(&CHACHA_CONSTANTS);
+    for (i, chunk) in key.chunks_exact(4).enumerate() {
+        initial[4 + i] = word(chunk);
+    }
+    initial[12] = counter;
+    for (i, chunk) in nonce.chunks_exact(4).enumerate() {
+        initial[13 + i] = word(chunk);
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        // Column rounds
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        // Diagonal rounds
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    for (word, initial_word) in state.iter_mut().zip(initial) {
+        *word = word.wrapping_add(initial_word);
+    }
+    state
+}
+
+/// ChaCha20 keystream generator
+/// Serializes as the seed and stream position; the current block is recomputed on demand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeededRng {
+    seed: [u8; 32],
+    /// Keystream words consumed so far
+    position: u64,
+    #[serde(skip)]
+    block: Option<(u64, [u32; 16])>,
+}
+
+impl SeededRng {
+    /// Create a generator from a small integer seed (tests and simulations)
+    pub fn new(seed: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        SeededRng::from_seed(bytes)
+    }
+
+    /// Create a generator keyed directly by a 32-byte seed
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        SeededRng {
+            seed,
+            position: 0,
+            block: None,
+        }
+    }
+
+    /// Create a generator for `purpose` from a public commitment such as a block hash
+    pub fn from_commitment(commitment: &[u8], purpose: &str) -> Self {
+        let mut encoder = Encoder::new();
+        encoder.str(purpose).bytes(commitment);
+        SeededRng::from_seed(tagged_hash(SEED_TAG, &encoder.finish()))
+    }
+
+    /// Next 32 random bits
+    pub fn next_u32(&mut self) -> u32 {
+        let block_index = self.position / 16;
+
+        let words = match self.block {
+            Some((index, words)) if index == block_index => words,
+            _ => {
+                // The low 32 bits of the block index are the counter, the high bits go in the
+                // nonce, so the stream never repeats
+                let mut nonce = [0u8; 12];
+                nonce[..4].copy_from_slice(&((block_index >> 32) as u32).to_le_bytes());
+                let words = chacha20_block(&self.seed, block_index as u32, &nonce);
+                self.block = Some((block_index, words));
+                words
+            }
+        };
+
+        let value = words[(self.position % 16) as usize];
+        self.position += 1;
+        value
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    /// Uniform sample in [0, 1) with 53 bits of precision
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_hex;
+
+    #[test]
+    fn test_chacha20_block_rfc8439_vector() {
+        // RFC 8439 section 2.3.2
+        let key: Vec<u8> = (0u8..32).collect();
+        let nonce = [0, 0, 0, 0x09, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let block = chacha20_block(&key.try_into().unwrap(), 1, &nonce);
+
+        let bytes: Vec<u8> = block.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(
+            to_hex(&bytes),
+            "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+             d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+        );
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        for _ in 0..40 {
+            let sample = a.next_f64();
+            assert_eq!(sample, b.next_f64());
+            assert!((0.0..1.0).contains(&sample));
+        }
+        assert_ne!(SeededRng::new(1).next_u64(), SeededRng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_keystream_matches_block_function() {
+        // Words come straight from the keystream, crossing into the next block
+        let mut rng = SeededRng::from_seed([7u8; 32]);
+        let first = chacha20_block(&[7u8; 32], 0, &[0u8; 12]);
+        let second = chacha20_block(&[7u8; 32], 1, &[0u8; 12]);
+
+        let words: Vec<u32> = (0..20).map(|_| rng.next_u32()).collect();
+        assert_eq!(&words[..16], &first);
+        assert_eq!(&words[16..], &second[..4]);
+    }
+
+    #[test]
+    fn test_seed_from_commitment() {
+        let block_hash = [0xab; 32];
+
+        // Same commitment and purpose: same stream; different purpose: independent stream
+        let mut a = SeededRng::from_commitment(&block_hash, "continuation");
+        let mut b = SeededRng::from_commitment(&block_hash, "continuation");
+        let mut c = SeededRng::from_commitment(&block_hash, "noise");
+        let first = a.next_u64();
+        assert_eq!(first, b.next_u64());
+        assert_ne!(first, c.next_u64());
+
+        // A serialized generator resumes where it left off
+        let json = serde_json::to_string(&a).unwrap();
+        let mut resumed: SeededRng = serde_json::from_str(&json).unwrap();
+        assert_eq!(resumed.next_u64(), a.next_u64());
+    }
+}
@@ -0,0 +1,141 @@
+/**
+ * SPELL COMMITMENT
+ *
+ * Defines exactly what the commit transaction's OP_RETURN commits to:
+ *
+ *   spell_data       = SPELL_VERSION || canonical_encoding(ProveOutput)
+ *   spell_commitment = SHA-256("trust-game/spell/v1" || spell_data)
+ *
+ * The zkVM program outputs the commitment alongside the reputation, so a
+ * verifier can match an on-chain OP_RETURN to a proven reputation.
+ */
+
+use crate::encoding::{tagged_hash, CanonicalEncode};
+use crate::prove::ProveOutput;
+
+/// Version byte prefixed to the spell encoding
+pub const SPELL_VERSION: u8 = 1;
+
+/// Domain tag for spell commitments
+const SPELL_TAG: &str = "trust-game/spell/v1";
+
+/// OP_RETURN opcode
+const OP_RETURN: u8 = 0x6a;
+
+/// Canonical spell encoding of a proven reputation (embedded in the spell tx witness)
+pub fn encode_spell(output: &ProveOutput) -> Vec<u8> {
+    let mut data = vec![SPELL_VERSION];
+    data.extend_from_slice(&output.canonical_bytes());
+    data
+}
+
+/// 32-byte commitment placed in the commit tx's OP_RETURN
+pub fn spell_commitment(output: &ProveOutput) -> [u8; 32] {
+    tagged_hash(SPELL_TAG, &encode_spell(output))
+}
+
+/// Script committing to a spell: OP_RETURN <32-byte spell commitment>
+pub fn commitment_script(commitment: &[u8; 32]) -> Vec<u8> {
+    let mut script = vec![OP_RETURN, 0x20];
+    script.extend_from_slice(commitment);
+    script
+}
+
+/// Check that an OP_RETURN script commits to this proven reputation
+pub fn matches_commitment_script(script: &[u8], output: &ProveOutput) -> bool {
+    script == commitment_script(&spell_commitment(output)).as_slice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prove::OpponentRecord;
+    use crate::GamePolicy;
+    use crate::encoding::to_hex;
+
+    fn sample_output() -> ProveOutput {
+        ProveOutput {
+            player_address: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+            total_moves: 5,
+            cooperative_moves: 3,
+            reputation_score: 60,
+            tier: 1,
+            voting_power: 60,
+            sessions: 1,
+            recursion_vkey: None,
+            opponents: vec![OpponentRecord {
+                opponent: String::new(),
+                total_moves: 5,
+                cooperative_moves: 3,
+                matches: 1,
+                payoff: 6,
+                opponent_payoff: 6,
+                countersigned_matches: 0,
+            }],
+            countersigned: false,
+            sybil_flags: Vec::new(),
+            game_policy: GamePolicy::default(),
+            game_ids: Vec::new(),
+            spell_commitment: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_spell_encoding() {
+        let data = encode_spell(&sample_output());
+
+        assert_eq!(data[0], SPELL_VERSION);
+        assert_eq!(
+            to_hex(&data[1..5]),
+            "0000002a" // Address length prefix
+        );
+        assert_eq!(
+            data.len(),
+            1 + 4 + 42 + 4 + 4 + 4 + 1 + 4 + 4 + 1 + 4 + 4 + 4 + 4 + 4 + 8 + 8 + 4
+                + 1 + 4 + 4 + 1 + 4
+        );
+    }
+
+    #[test]
+    fn test_spell_commitment_golden_vector() {
+        assert_eq!(
+            to_hex(&spell_commitment(&sample_output())),
+            "1e66d03473ab40cf876a068653fe262fa0dcea09db34ede3bdd30c43df9b0e73"
+        );
+    }
+
+    #[test]
+    fn test_commitment_binds_every_field() {
+        let base = spell_commitment(&sample_output());
+
+        let mut output = sample_output();
+        output.voting_power = 61;
+        assert_ne!(spell_commitment(&output), base);
+
+        let mut output = sample_output();
+        output.tier = 2;
+        assert_ne!(spell_commitment(&output), base);
+
+        let mut output = sample_output();
+        output.player_address.push('x');
+        assert_ne!(spell_commitment(&output), base);
+
+        // The reported commitment itself is not part of the encoding
+        let mut output = sample_output();
+        output.spell_commitment = to_hex(&base);
+        assert_eq!(spell_commitment(&output), base);
+    }
+
+    #[test]
+    fn test_matches_commitment_script() {
+        let output = sample_output();
+        let script = commitment_script(&spell_commitment(&output));
+
+        assert_eq!(script.len(), 34);
+        assert!(matches_commitment_script(&script, &output));
+
+        let mut other = sample_output();
+        other.reputation_score = 100;
+        assert!(!matches_commitment_script(&script, &other));
+    }
+}
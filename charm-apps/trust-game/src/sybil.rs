@@ -0,0 +1,180 @@
+/**
+ * SYBIL RESISTANCE
+ *
+ * Heuristics against reputation farming: a player can create a second address
+ * and play cooperative games against themselves. Addresses are free, so this
+ * cannot be prevented outright, but it can be made expensive and visible:
+ * - Matches against the same opponent beyond a limit are ignored
+ * - Higher tiers require a minimum number of distinct opponents
+ * - Only opponents who countersigned a transcript count as distinct; unsigned
+ *   matches name opponents the prover made up, so they share a single limit
+ * - Perfectly mirrored histories (both sides played exactly the same moves)
+ *   are flagged
+ *
+ * Every heuristic that fires is reported as a `SybilFlag` in the proof output,
+ * so verifiers and governance can see why a reputation was limited.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::encoding::{CanonicalEncode, Decoder, Encoder};
+use crate::MoveHistory;
+
+/// Thresholds for the reputation-farming heuristics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SybilPolicy {
+    /// Matches counted per countersigning opponent, and in total for unsigned matches;
+    /// further matches are ignored
+    pub max_matches_per_opponent: u32,
+    /// Distinct countersigning opponents required for each tier (Suspicious, Neutral, Trusted)
+    pub min_opponents: [u32; 3],
+    /// Shortest match whose mirrored moves are flagged (mutual cooperation over a few
+    /// rounds is common between honest players)
+    pub min_mirrored_moves: u32,
+}
+
+impl Default for SybilPolicy {
+    fn default() -> Self {
+        SybilPolicy {
+            max_matches_per_opponent: 10,
+            min_opponents: [0, 0, 3],
+            min_mirrored_moves: 10,
+        }
+    }
+}
+
+impl SybilPolicy {
+    /// Highest tier reachable with `opponents` distinct countersigning opponents
+    pub fn max_tier(&self, opponents: u32) -> u8 {
+        (0..self.min_opponents.len())
+            .rev()
+            .find(|tier| opponents >= self.min_opponents[*tier])
+            .unwrap_or(0) as u8
+    }
+
+    /// Whether a match of `moves` against `opponent_moves` is suspiciously mirrored
+    pub fn is_mirrored(&self, moves: &MoveHistory, opponent_moves: &MoveHistory) -> bool {
+        moves.len() >= self.min_mirrored_moves && moves == opponent_moves
+    }
+}
+
+/// Why a reputation was limited or looks farmed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SybilFlag {
+    /// Matches against `opponent` beyond the per-opponent limit were ignored
+    ExcessMatches { opponent: String, ignored: u32 },
+    /// Tier was capped at `tier` because only `opponents` distinct opponents countersigned
+    TooFewOpponents { opponents: u32, tier: u8 },
+    /// Both sides of a match against `opponent` played exactly the same moves
+    MirroredHistory { opponent: String, moves: u32 },
+}
+
+impl fmt::Display for SybilFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SybilFlag::ExcessMatches { opponent, ignored } => write!(
+                f,
+                "Ignored {} matches against {:?} beyond the per-opponent limit",
+                ignored, opponent
+            ),
+            SybilFlag::TooFewOpponents { opponents, tier } => write!(
+                f,
+                "Tier capped at {} with only {} distinct countersigned opponents",
+                tier, opponents
+            ),
+            SybilFlag::MirroredHistory { opponent, moves } => write!(
+                f,
+                "Match against {:?} is perfectly mirrored over {} moves",
+                opponent, moves
+            ),
+        }
+    }
+}
+
+impl CanonicalEncode for SybilFlag {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            SybilFlag::ExcessMatches { opponent, ignored } => {
+                encoder.u8(0).str(opponent).u32(*ignored);
+            }
+            SybilFlag::TooFewOpponents { opponents, tier } => {
+                encoder.u8(1).u32(*opponents).u8(*tier);
+            }
+            SybilFlag::MirroredHistory { opponent, moves } => {
+                encoder.u8(2).str(opponent).u32(*moves);
+            }
+        }
+    }
+}
+
+impl SybilFlag {
+    /// Decode a flag written by `CanonicalEncode`
+    pub fn decode(decoder: &mut Decoder) -> Result<Self, String> {
+        match decoder.u8()? {
+            0 => Ok(SybilFlag::ExcessMatches {
+                opponent: decoder.str()?,
+                ignored: decoder.u32()?,
+            }),
+            1 => Ok(SybilFlag::TooFewOpponents {
+                opponents: decoder.u32()?,
+                tier: decoder.u8()?,
+            }),
+            2 => Ok(SybilFlag::MirroredHistory {
+                opponent: decoder.str()?,
+                moves: decoder.u32()?,
+            }),
+            other => Err(format!("Invalid sybil flag tag {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_tier_by_opponents() {
+        let policy = SybilPolicy::default();
+        assert_eq!(policy.max_tier(0), 1);
+        assert_eq!(policy.max_tier(2), 1);
+        assert_eq!(policy.max_tier(3), 2);
+
+        let strict = SybilPolicy {
+            min_opponents: [0, 2, 5],
+            ..SybilPolicy::default()
+        };
+        assert_eq!(strict.max_tier(1), 0);
+    }
+
+    #[test]
+    fn test_mirrored_histories() {
+        let policy = SybilPolicy::default();
+        let cooperate = MoveHistory::from_values(&[0; 12]).unwrap();
+        let mut varied = cooperate.clone();
+        varied.push(crate::Move::Defect);
+
+        assert!(policy.is_mirrored(&cooperate, &cooperate));
+        assert!(!policy.is_mirrored(&varied, &cooperate));
+
+        // Short matches are not flagged
+        let short = MoveHistory::from_values(&[0; 5]).unwrap();
+        assert!(!policy.is_mirrored(&short, &short));
+    }
+
+    #[test]
+    fn test_flag_encoding_roundtrip() {
+        let flags = [
+            SybilFlag::ExcessMatches { opponent: "alice".to_string(), ignored: 2 },
+            SybilFlag::TooFewOpponents { opponents: 1, tier: 1 },
+            SybilFlag::MirroredHistory { opponent: String::new(), moves: 20 },
+        ];
+
+        for flag in flags {
+            let bytes = flag.canonical_bytes();
+            let mut decoder = Decoder::new(&bytes);
+            assert_eq!(SybilFlag::decode(&mut decoder).unwrap(), flag);
+            decoder.finish().unwrap();
+        }
+    }
+}
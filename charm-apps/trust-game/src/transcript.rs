@@ -0,0 +1,508 @@
+/**
+ * SIGNED GAME TRANSCRIPTS
+ *
+ * Move arrays supplied by one player prove nothing about the opponent: the
+ * prover could invent them. A `GameTranscript` instead carries every round's
+ * moves signed by BOTH players with BIP340 Schnorr signatures over secp256k1,
+ * so a match only counts if the opponent really played it.
+ *
+ * What each player signs, per round:
+ *   game_id = SHA-256("trust-game/transcript/v1" || header)
+ *   message = SHA-256("trust-game/transcript-round/v1" || game_id || round || move_1 || move_2)
+ *
+ * The header fixes both players, their keys, the payoffs, the number of rounds
+ * and a nonce chosen for the game, so signatures cannot be replayed in another
+ * game or round, and a transcript cannot be cut short to drop unfavourable rounds.
+ * Each player's address must be the taproot (P2TR) address of their key on the
+ * expected network, so a prover cannot attach freshly generated keys to addresses
+ * they do not control, nor re-encode one key under several network prefixes.
+ * Signatures are verified inside the zkVM guest (see `prove::validate_history`).
+ */
+
+use k256::schnorr::signature::hazmat::PrehashVerifier;
+use k256::schnorr::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::address::{address_hrp, is_p2tr_address};
+use crate::encoding::{from_hex, tagged_hash, to_hex, CanonicalEncode, Decoder, Encoder};
+use crate::prove::MatchHistory;
+use crate::{Move, MoveHistory};
+
+/// Domain tag for the game id
+const GAME_TAG: &str = "trust-game/transcript/v1";
+
+/// Domain tag for per-round messages
+const ROUND_TAG: &str = "trust-game/transcript-round/v1";
+
+/// 64-byte BIP340 signature, serialized as hex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SchnorrSignature(pub [u8; 64]);
+
+impl TryFrom<String> for SchnorrSignature {
+    type Error = String;
+
+    fn try_from(hex: String) -> Result<Self, String> {
+        let bytes: [u8; 64] = from_hex(&hex)?
+            .try_into()
+            .map_err(|_| "Expected a 64-byte signature".to_string())?;
+        Ok(SchnorrSignature(bytes))
+    }
+}
+
+impl From<SchnorrSignature> for String {
+    fn from(signature: SchnorrSignature) -> Self {
+        to_hex(&signature.0)
+    }
+}
+
+/// A transcript participant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptPlayer {
+    /// Taproot address of `public_key`
+    pub address: String,
+    /// BIP340 x-only public key signing this player's rounds
+    pub public_key: [u8; 32],
+}
+
+/// One round's moves and both players' signatures over them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRound {
+    pub move_1: Move,
+    pub move_2: Move,
+    /// Player 1's signature (None if they never signed)
+    pub signature_1: Option<SchnorrSignature>,
+    /// Player 2's signature (None if they never signed)
+    pub signature_2: Option<SchnorrSignature>,
+}
+
+/// A complete two-player game, countersigned round by round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameTranscript {
+    pub player_1: TranscriptPlayer,
+    pub player_2: TranscriptPlayer,
+    /// Player 1's payoff matrix [R, T, S, P]
+    pub payoffs: [i32; 4],
+    /// Player 2's payoff matrix [R, T, S, P] in an asymmetric game
+    #[serde(default)]
+    pub payoffs_2: Option<[i32; 4]>,
+    /// Number of rounds agreed for this game
+    pub total_rounds: u32,
+    /// Distinguishes repeated games between the same players
+    pub nonce: u64,
+    pub rounds: Vec<SignedRound>,
+}
+
+impl GameTranscript {
+    /// Identifier of this game, binding every signature to its header
+    pub fn game_id(&self) -> [u8; 32] {
+        let mut encoder = Encoder::new();
+        self.encode_header(&mut encoder);
+        tagged_hash(GAME_TAG, &encoder.finish())
+    }
+
+    /// Message both players sign for round `round`
+    pub fn round_message(game_id: &[u8; 32], round: u32, move_1: Move, move_2: Move) -> [u8; 32] {
+        let mut data = game_id.to_vec();
+        data.extend_from_slice(&round.to_be_bytes());
+        data.push(move_1 as u8);
+        data.push(move_2 as u8);
+        tagged_hash(ROUND_TAG, &data)
+    }
+
+    /// Check that every agreed round is present and signed by both players, whose
+    /// addresses must be on network `hrp`
+    pub fn verify(&self, hrp: &str) -> Result<(), String> {
+        if self.player_1.public_key == self.player_2.public_key {
+            return Err("Players must sign with different keys".to_string());
+        }
+        for player in [&self.player_1, &self.player_2] {
+            if !is_p2tr_address(&player.address, hrp, &player.public_key) {
+                return Err(format!(
+                    "{} is not the {} taproot address of its key",
+                    player.address, hrp
+                ));
+            }
+        }
+        // An empty game needs no signatures, so it would prove nothing about the opponent
+        if self.total_rounds == 0 {
+            return Err("Transcript has no rounds".to_string());
+        }
+        if self.rounds.len() != self.total_rounds as usize {
+            return Err(format!(
+                "Transcript has {} of {} rounds",
+                self.rounds.len(),
+                self.total_rounds
+            ));
+        }
+
+        let key_1 = verifying_key(&self.player_1)?;
+        let key_2 = verifying_key(&self.player_2)?;
+        let game_id = self.game_id();
+
+        for (i, round) in self.rounds.iter().enumerate() {
+            let message =
+                GameTranscript::round_message(&game_id, i as u32, round.move_1, round.move_2);
+            for (player, key, signature) in
+                [(1, &key_1, &round.signature_1), (2, &key_2, &round.signature_2)]
+            {
+                let signature = signature.ok_or_else(|| {
+                    format!("Round {}: missing signature from player {}", i, player)
+                })?;
+                let invalid = |_| format!("Round {}: invalid signature from player {}", i, player);
+                let signature = Signature::try_from(&signature.0[..]).map_err(invalid)?;
+                key.verify_prehash(&message, &signature).map_err(invalid)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify the transcript and view it as a match played by `address`, on the
+    /// network of `address`
+    /// The opponent is identified by their hex x-only key, one identity per key
+    pub fn match_for(&self, address: &str) -> Result<MatchHistory, String> {
+        self.verify(address_hrp(address)?)?;
+
+        let moves_1: MoveHistory = self.rounds.iter().map(|round| round.move_1).collect();
+        let moves_2: MoveHistory = self.rounds.iter().map(|round| round.move_2).collect();
+        let payoffs_2 = self.payoffs_2.unwrap_or(self.payoffs);
+
+        if address == self.player_1.address {
+            Ok(MatchHistory {
+                opponent: to_hex(&self.player_2.public_key),
+                moves: moves_1,
+                opponent_moves: moves_2,
+                payoffs: self.payoffs,
+                opponent_payoffs: self.payoffs_2,
+            })
+        } else if address == self.player_2.address {
+            Ok(MatchHistory {
+                opponent: to_hex(&self.player_1.public_key),
+                moves: moves_2,
+                opponent_moves: moves_1,
+                payoffs: payoffs_2,
+                opponent_payoffs: self.payoffs_2.map(|_| self.payoffs),
+            })
+        } else {
+            Err("Player is not part of this transcript".to_string())
+        }
+    }
+
+    fn encode_header(&self, encoder: &mut Encoder) {
+        for player in [&self.player_1, &self.player_2] {
+            player.encode(encoder);
+        }
+        for value in self.payoffs {
+            encoder.i32(value);
+        }
+        match self.payoffs_2 {
+            Some(payoffs_2) => {
+                encoder.u8(1);
+                for value in payoffs_2 {
+                    encoder.i32(value);
+                }
+            }
+            None => {
+                encoder.u8(0);
+            }
+        }
+        encoder.u32(self.total_rounds).u64(self.nonce);
+    }
+
+    /// Decode a transcript written by `CanonicalEncode`
+    pub fn decode(decoder: &mut Decoder) -> Result<Self, String> {
+        let player_1 = TranscriptPlayer::decode(decoder)?;
+        let player_2 = TranscriptPlayer::decode(decoder)?;
+        let payoffs = decode_payoffs(decoder)?;
+        let payoffs_2 = match decoder.u8()? {
+            0 => None,
+            1 => Some(decode_payoffs(decoder)?),
+            other => return Err(format!("Invalid option tag {}", other)),
+        };
+        let total_rounds = decoder.u32()?;
+        let nonce = decoder.u64()?;
+
+        let mut rounds = Vec::new();
+        for _ in 0..decoder.u32()? {
+            rounds.push(SignedRound {
+                move_1: decode_move(decoder)?,
+                move_2: decode_move(decoder)?,
+                signature_1: decode_signature(decoder)?,
+                signature_2: decode_signature(decoder)?,
+            });
+        }
+
+        Ok(GameTranscript {
+            player_1,
+            player_2,
+            payoffs,
+            payoffs_2,
+            total_rounds,
+            nonce,
+            rounds,
+        })
+    }
+}
+
+fn verifying_key(player: &TranscriptPlayer) -> Result<VerifyingKey, String> {
+    VerifyingKey::from_bytes(&player.public_key)
+        .map_err(|_| format!("Invalid public key for {}", player.address))
+}
+
+fn decode_payoffs(decoder: &mut Decoder) -> Result<[i32; 4], String> {
+    Ok([decoder.i32()?, decoder.i32()?, decoder.i32()?, decoder.i32()?])
+}
+
+fn decode_move(decoder: &mut Decoder) -> Result<Move, String> {
+    match decoder.u8()? {
+        0 => Ok(Move::Cooperate),
+        1 => Ok(Move::Defect),
+        other => Err(format!("Invalid move {}", other)),
+    }
+}
+
+fn encode_signature(encoder: &mut Encoder, signature: &Option<SchnorrSignature>) {
+    match signature {
+        Some(signature) => {
+            encoder.u8(1);
+            for byte in signature.0 {
+                encoder.u8(byte);
+            }
+        }
+        None => {
+            encoder.u8(0);
+        }
+    }
+}
+
+fn decode_signature(decoder: &mut Decoder) -> Result<Option<SchnorrSignature>, String> {
+    match decoder.u8()? {
+        0 => Ok(None),
+        1 => {
+            let mut bytes = [0u8; 64];
+            bytes.copy_from_slice(decoder.take(64)?);
+            Ok(Some(SchnorrSignature(bytes)))
+        }
+        other => Err(format!("Invalid option tag {}", other)),
+    }
+}
+
+impl TranscriptPlayer {
+    fn decode(decoder: &mut Decoder) -> Result<Self, String> {
+        Ok(TranscriptPlayer {
+            address: decoder.str()?,
+            public_key: decoder.hash()?,
+        })
+    }
+}
+
+impl CanonicalEncode for TranscriptPlayer {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.address);
+        for byte in self.public_key {
+            encoder.u8(byte);
+        }
+    }
+}
+
+impl CanonicalEncode for SignedRound {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.u8(self.move_1 as u8).u8(self.move_2 as u8);
+        encode_signature(encoder, &self.signature_1);
+        encode_signature(encoder, &self.signature_2);
+    }
+}
+
+impl CanonicalEncode for GameTranscript {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.encode_header(encoder);
+        let rounds: Vec<&SignedRound> = self.rounds.iter().collect();
+        encoder.seq(&rounds);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::address::p2tr_address;
+    use k256::schnorr::SigningKey;
+
+    pub(crate) fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32]).unwrap()
+    }
+
+    /// Testnet taproot address of `signing_key(seed)`
+    pub(crate) fn address(seed: u8) -> String {
+        address_on("tb", seed)
+    }
+
+    /// Taproot address of `signing_key(seed)` on network `hrp`
+    pub(crate) fn address_on(hrp: &str, seed: u8) -> String {
+        p2tr_address(hrp, &signing_key(seed).verifying_key().to_bytes().into())
+    }
+
+    fn player(address: &str, key: &SigningKey) -> TranscriptPlayer {
+        TranscriptPlayer {
+            address: address.to_string(),
+            public_key: key.verifying_key().to_bytes().into(),
+        }
+    }
+
+    fn sign(key: &SigningKey, message: &[u8; 32]) -> Option<SchnorrSignature> {
+        let signature = key.sign_prehash_with_aux_rand(message, &[0u8; 32]).unwrap();
+        Some(SchnorrSignature(signature.to_bytes()))
+    }
+
+    /// Transcript between the players of keys 1 and 2, signed by both
+    pub(crate) fn signed_transcript(moves: &[(Move, Move)]) -> GameTranscript {
+        signed_game(moves, 1)
+    }
+
+    /// Signed transcript of the game with nonce `nonce`
+    pub(crate) fn signed_game(moves: &[(Move, Move)], nonce: u64) -> GameTranscript {
+        signed_between((1, 2), moves, nonce)
+    }
+
+    /// Signed transcript between the players of keys `seeds`
+    pub(crate) fn signed_between(
+        seeds: (u8, u8),
+        moves: &[(Move, Move)],
+        nonce: u64,
+    ) -> GameTranscript {
+        signed_on(("tb", "tb"), seeds, moves, nonce)
+    }
+
+    /// Signed transcript with each player's address on the given network
+    pub(crate) fn signed_on(
+        hrps: (&str, &str),
+        seeds: (u8, u8),
+        moves: &[(Move, Move)],
+        nonce: u64,
+    ) -> GameTranscript {
+        let (key_1, key_2) = (signing_key(seeds.0), signing_key(seeds.1));
+        let mut transcript = GameTranscript {
+            player_1: player(&address_on(hrps.0, seeds.0), &key_1),
+            player_2: player(&address_on(hrps.1, seeds.1), &key_2),
+            payoffs: [2, 3, -1, 0],
+            payoffs_2: None,
+            total_rounds: moves.len() as u32,
+            nonce,
+            rounds: Vec::new(),
+        };
+
+        let game_id = transcript.game_id();
+        for (i, (move_1, move_2)) in moves.iter().enumerate() {
+            let message = GameTranscript::round_message(&game_id, i as u32, *move_1, *move_2);
+            transcript.rounds.push(SignedRound {
+                move_1: *move_1,
+                move_2: *move_2,
+                signature_1: sign(&key_1, &message),
+                signature_2: sign(&key_2, &message),
+            });
+        }
+        transcript
+    }
+
+    #[test]
+    fn test_bip340_test_vector() {
+        // BIP340 test vector 0
+        let mut secret = [0u8; 32];
+        secret[31] = 3;
+        let key = SigningKey::from_bytes(&secret).unwrap();
+        let signature = key.sign_prehash_with_aux_rand(&[0u8; 32], &[0u8; 32]).unwrap();
+        assert_eq!(
+            to_hex(&signature.to_bytes()),
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0"
+        );
+    }
+
+    #[test]
+    fn test_countersigned_transcript() {
+        let transcript = signed_transcript(&[
+            (Move::Cooperate, Move::Cooperate),
+            (Move::Cooperate, Move::Defect),
+            (Move::Defect, Move::Cooperate),
+        ]);
+        assert!(transcript.verify("tb").is_ok());
+
+        // Each player sees the match from their side
+        let bob = transcript.match_for(&address(2)).unwrap();
+        assert_eq!(bob.opponent, to_hex(&transcript.player_1.public_key));
+        assert_eq!(bob.moves.cooperations(), 2);
+        assert_eq!(bob.opponent_moves.defections(), 1);
+        assert!(transcript.match_for("carol").is_err());
+
+        // Binary roundtrip
+        let bytes = transcript.canonical_bytes();
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(GameTranscript::decode(&mut decoder).unwrap(), transcript);
+        decoder.finish().unwrap();
+
+        // JSON carries signatures as hex
+        let json = serde_json::to_string(&transcript).unwrap();
+        assert_eq!(serde_json::from_str::<GameTranscript>(&json).unwrap(), transcript);
+    }
+
+    #[test]
+    fn test_rejects_unsigned_or_altered_transcripts() {
+        let moves = [(Move::Cooperate, Move::Cooperate), (Move::Cooperate, Move::Cooperate)];
+
+        // Missing counter-signature
+        let mut missing = signed_transcript(&moves);
+        missing.rounds[1].signature_2 = None;
+        assert_eq!(
+            missing.verify("tb").unwrap_err(),
+            "Round 1: missing signature from player 2"
+        );
+
+        // Rewriting the opponent's move invalidates their signature
+        let mut altered = signed_transcript(&moves);
+        altered.rounds[0].move_2 = Move::Defect;
+        assert!(altered.verify("tb").is_err());
+
+        // Dropping rounds breaks the agreed length
+        let mut truncated = signed_transcript(&moves);
+        truncated.rounds.pop();
+        assert!(truncated.verify("tb").is_err());
+
+        // An empty game carries no signatures at all
+        assert_eq!(
+            signed_transcript(&[]).verify("tb").unwrap_err(),
+            "Transcript has no rounds"
+        );
+
+        // Signatures from one game do not carry over to another
+        let mut replayed = signed_transcript(&moves);
+        replayed.nonce = 2;
+        assert!(replayed.verify("tb").is_err());
+    }
+
+    #[test]
+    fn test_rejects_keys_not_matching_addresses() {
+        let moves = [(Move::Cooperate, Move::Cooperate)];
+
+        // A fresh key signing for someone else's address
+        let mut impersonated = signed_transcript(&moves);
+        impersonated.player_2.address = address(3);
+        assert_eq!(
+            impersonated.verify("tb").unwrap_err(),
+            format!("{} is not the tb taproot address of its key", address(3))
+        );
+
+        // A correctly signed game on another network is rejected
+        let mainnet = signed_on(("tb", "bc"), (1, 2), &moves, 1);
+        assert_eq!(
+            mainnet.verify("tb").unwrap_err(),
+            format!("{} is not the tb taproot address of its key", address_on("bc", 2))
+        );
+        assert!(mainnet.match_for(&address(1)).is_err());
+        assert!(signed_on(("bc", "bc"), (1, 2), &moves, 1).verify("bc").is_ok());
+
+        // Placeholder names are not addresses
+        let mut named = signed_transcript(&moves);
+        named.player_1.address = "alice".to_string();
+        assert!(named.verify("tb").is_err());
+    }
+}
@@ -0,0 +1,968 @@
+/**
+ * GOVERNANCE STATE TRANSITIONS
+ *
+ * Proves governance the same way reputation is proven: the zkVM takes a prior
+ * governance state root, the full prior state, and a batch of actions, applies
+ * the actions, and outputs the new state root plus tally results.
+ *
+ * A verifier only needs the two roots and the actions hash from the output to
+ * check that a proposal outcome followed from a committed state.
+ *
+ * Proposer tiers and voting power are not asserted by the prover: each
+ * proposal and vote carries the player's reputation `ProveOutput`, which the
+ * guest verifies with SP1 recursion against `reputation_vkey`. The output commits that key, so
+ * verifiers must check it is the trust-game program's own key. Only countersigned
+ * reputations carry voting power (see `ProveOutput::governance_voting_power`).
+ *
+ * Proposals and votes are authorized by the acting player: a BIP340 signature, by the
+ * key behind their taproot address, over the batch's prior state root and the
+ * action's content. Public reputation proofs alone cannot be used to act for a player,
+ * and a signature cannot be replayed once the state has moved on.
+ */
+
+use k256::schnorr::signature::hazmat::PrehashVerifier;
+use k256::schnorr::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::address::{address_hrp, is_p2tr_address};
+use crate::encoding::{hash_from_hex, tagged_hash, to_hex, CanonicalEncode, Encoder};
+use crate::governance::{GovernanceState, ProposalType, TallyMethod, Vote};
+use crate::prove::ProveOutput;
+use crate::transcript::SchnorrSignature;
+use crate::PayoffMatrix;
+
+/// Domain tag for the hash of an action batch
+const ACTIONS_TAG: &str = "trust-game/governance-actions/v1";
+
+/// Domain tag for the message a player signs to authorize an action
+const AUTHORIZATION_TAG: &str = "trust-game/governance-authorization/v1";
+
+/// A player's signature authorizing a governance action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Authorization {
+    /// BIP340 x-only key whose taproot address is the acting player's
+    pub public_key: [u8; 32],
+    /// Signature over `GovernanceAction::signing_message`
+    pub signature: SchnorrSignature,
+}
+
+impl Authorization {
+    /// Check that `address` belongs to the signing key and the signature covers `message`
+    fn verify(&self, address: &str, message: &[u8; 32]) -> Result<(), String> {
+        if !is_p2tr_address(address, address_hrp(address)?, &self.public_key) {
+            return Err(format!("{} is not the taproot address of the signing key", address));
+        }
+        let invalid = |_| format!("Invalid signature from {}", address);
+        let key = VerifyingKey::from_bytes(&self.public_key).map_err(invalid)?;
+        let signature = Signature::try_from(&self.signature.0[..]).map_err(invalid)?;
+        key.verify_prehash(message, &signature).map_err(invalid)
+    }
+}
+
+impl CanonicalEncode for Authorization {
+    fn encode(&self, encoder: &mut Encoder) {
+        for byte in self.public_key.iter().chain(self.signature.0.iter()) {
+            encoder.u8(*byte);
+        }
+    }
+}
+
+/// A single governance operation applied inside the zkVM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GovernanceAction {
+    /// Submit a Yes/No proposal
+    CreateProposal {
+        proposer: String,
+        proposer_reputation: ProveOutput,
+        proposal_type: ProposalType,
+        description: String,
+        deposit: u64,
+        authorization: Authorization,
+    },
+    /// Submit a proposal choosing between several options
+    CreateMultiOptionProposal {
+        proposer: String,
+        proposer_reputation: ProveOutput,
+        proposal_type: ProposalType,
+        description: String,
+        options: Vec<String>,
+        tally_method: TallyMethod,
+        deposit: u64,
+        authorization: Authorization,
+    },
+    /// Cast a Yes/No/Abstain vote
+    Vote {
+        proposal_id: u32,
+        address: String,
+        vote: Vote,
+        voter_reputation: ProveOutput,
+        timestamp: u64,
+        authorization: Authorization,
+    },
+    /// Cast an option ballot
+    VoteOptions {
+        proposal_id: u32,
+        address: String,
+        choices: Vec<u32>,
+        voter_reputation: ProveOutput,
+        timestamp: u64,
+        authorization: Authorization,
+    },
+    /// Move a proposal to its next voting round
+    AdvanceRound { proposal_id: u32 },
+    /// Execute a proposal (fails the batch if it has already been executed)
+    ExecuteProposal { proposal_id: u32 },
+    /// Refund or forfeit a closed proposal's deposit
+    SettleDeposit { proposal_id: u32 },
+    /// Register an app that depends on this reputation system
+    RegisterDependentApp {
+        app_id: String,
+        app_name: String,
+        min_reputation_tier: u8,
+    },
+    /// Submit a proposal to change the payoff matrix (checked against the game policy)
+    CreatePayoffProposal {
+        proposer: String,
+        proposer_reputation: ProveOutput,
+        description: String,
+        payoff_matrix: PayoffMatrix,
+        deposit: u64,
+        authorization: Authorization,
+    },
+}
+
+impl GovernanceAction {
+    /// Reputation proof this action relies on, verified by the guest
+    pub fn reputation_proof(&self) -> Option<&ProveOutput> {
+        match self {
+            GovernanceAction::CreateProposal { proposer_reputation, .. }
+            | GovernanceAction::CreateMultiOptionProposal { proposer_reputation, .. }
+            | GovernanceAction::CreatePayoffProposal { proposer_reputation, .. } => {
+                Some(proposer_reputation)
+            }
+            GovernanceAction::Vote { voter_reputation, .. }
+            | GovernanceAction::VoteOptions { voter_reputation, .. } => Some(voter_reputation),
+            _ => None,
+        }
+    }
+
+    /// Player acting and their authorization, for proposals and votes
+    pub fn signer(&self) -> Option<(&str, &Authorization)> {
+        match self {
+            GovernanceAction::CreateProposal { proposer, authorization, .. }
+            | GovernanceAction::CreateMultiOptionProposal { proposer, authorization, .. }
+            | GovernanceAction::CreatePayoffProposal { proposer, authorization, .. } => {
+                Some((proposer, authorization))
+            }
+            GovernanceAction::Vote { address, authorization, .. }
+            | GovernanceAction::VoteOptions { address, authorization, .. } => {
+                Some((address, authorization))
+            }
+            _ => None,
+        }
+    }
+
+    /// Message the acting player signs: the batch's prior state root and the action's
+    /// content (everything but the authorization itself)
+    pub fn signing_message(&self, prior_state_root: &[u8; 32]) -> [u8; 32] {
+        let mut encoder = Encoder::new();
+        self.encode_content(&mut encoder);
+        let mut data = prior_state_root.to_vec();
+        data.extend_from_slice(&encoder.finish());
+        tagged_hash(AUTHORIZATION_TAG, &data)
+    }
+
+    /// Proposal this action touches, if any (new proposals are resolved after apply)
+    fn proposal_id(&self) -> Option<u32> {
+        match self {
+            GovernanceAction::Vote { proposal_id, .. }
+            | GovernanceAction::VoteOptions { proposal_id, .. }
+            | GovernanceAction::AdvanceRound { proposal_id }
+            | GovernanceAction::ExecuteProposal { proposal_id }
+            | GovernanceAction::SettleDeposit { proposal_id } => Some(*proposal_id),
+            _ => None,
+        }
+    }
+
+    /// Apply this action to a governance state, checking the player's authorization
+    /// against `prior_state_root`, the root of the state the batch builds on
+    /// Returns the ID of the proposal it touched, if any
+    pub fn apply(
+        &self,
+        state: &mut GovernanceState,
+        prior_state_root: &[u8; 32],
+    ) -> Result<Option<u32>, String> {
+        if let Some((address, authorization)) = self.signer() {
+            authorization.verify(address, &self.signing_message(prior_state_root))?;
+        }
+
+        match self.clone() {
+            GovernanceAction::CreateProposal {
+                proposer,
+                proposer_reputation,
+                proposal_type,
+                description,
+                deposit,
+                ..
+            } => {
+                let proposer_tier = proven_reputation(&proposer, &proposer_reputation)?.tier;
+                state
+                    .create_player_proposal(
+                        proposer,
+                        proposer_tier,
+                        proposal_type,
+                        description,
+                        deposit,
+                    )
+                    .map(Some)
+            }
+            GovernanceAction::CreateMultiOptionProposal {
+                proposer,
+                proposer_reputation,
+                proposal_type,
+                description,
+                options,
+                tally_method,
+                deposit,
+                ..
+            } => {
+                let proposer_tier = proven_reputation(&proposer, &proposer_reputation)?.tier;
+                state
+                    .create_multi_option_proposal(
+                        proposer,
+                        proposer_tier,
+                        proposal_type,
+                        description,
+                        options,
+                        tally_method,
+                        deposit,
+                    )
+                    .map(Some)
+            }
+            GovernanceAction::Vote {
+                proposal_id,
+                address,
+                vote,
+                voter_reputation,
+                timestamp,
+                ..
+            } => {
+                let reputation = proven_reputation(&address, &voter_reputation)?;
+                state
+                    .vote(
+                        proposal_id,
+                        address,
+                        vote,
+                        reputation.reputation_score,
+                        reputation.governance_voting_power(),
+                        timestamp,
+                    )
+                    .map(|_| self.proposal_id())
+            }
+            GovernanceAction::VoteOptions {
+                proposal_id,
+                address,
+                choices,
+                voter_reputation,
+                timestamp,
+                ..
+            } => {
+                let reputation = proven_reputation(&address, &voter_reputation)?;
+                state
+                    .vote_options(
+                        proposal_id,
+                        address,
+                        choices,
+                        reputation.reputation_score,
+                        reputation.governance_voting_power(),
+                        timestamp,
+                    )
+                    .map(|_| self.proposal_id())
+            }
+            GovernanceAction::AdvanceRound { proposal_id } => state
+                .advance_voting_round(proposal_id)
+                .map(|_| self.proposal_id()),
+            GovernanceAction::ExecuteProposal { proposal_id } => state
+                .execute_proposal(proposal_id)
+                .map(|_| self.proposal_id()),
+            GovernanceAction::SettleDeposit { proposal_id } => state
+                .settle_deposit(proposal_id)
+                .map(|_| self.proposal_id()),
+            GovernanceAction::RegisterDependentApp {
+                app_id,
+                app_name,
+                min_reputation_tier,
+            } => state
+                .register_dependent_app(app_id, app_name, min_reputation_tier)
+                .map(|_| None),
+            GovernanceAction::CreatePayoffProposal {
+                proposer,
+                proposer_reputation,
+                description,
+                payoff_matrix,
+                deposit,
+                ..
+            } => {
+                let proposer_tier = proven_reputation(&proposer, &proposer_reputation)?.tier;
+                state
+                    .create_payoff_proposal(
+                        proposer,
+                        proposer_tier,
+                        description,
+                        payoff_matrix,
+                        deposit,
+                    )
+                    .map(Some)
+            }
+        }
+    }
+}
+
+/// A player's proven reputation output (rejects proofs for another address)
+fn proven_reputation<'a>(
+    address: &str,
+    reputation: &'a ProveOutput,
+) -> Result<&'a ProveOutput, String> {
+    if reputation.player_address != address {
+        return Err(format!(
+            "Reputation proof is for {}, not {}",
+            reputation.player_address, address
+        ));
+    }
+    Ok(reputation)
+}
+
+/// Input to the governance zkVM program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionInput {
+    /// Hex state root the batch builds on (must match `prior_state`)
+    pub prior_state_root: String,
+    /// Full governance state committed to by `prior_state_root`
+    pub prior_state: GovernanceState,
+    /// Actions to apply, in order
+    pub actions: Vec<GovernanceAction>,
+    /// Verifying key digest of the trust-game program that produced the reputation proofs
+    pub reputation_vkey: [u32; 8],
+}
+
+/// Tally of a proposal touched by the batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalTally {
+    /// Proposal ID
+    pub proposal_id: u32,
+    /// Voting power for, against and abstaining (Yes/No proposals)
+    pub yes_voting_power: u32,
+    pub no_voting_power: u32,
+    pub abstain_voting_power: u32,
+    /// Voting power per option (multi-option proposals)
+    pub option_voting_power: Vec<u32>,
+    /// Winning option (multi-option proposals)
+    pub winning_option: Option<u32>,
+    /// Hex Merkle root over the votes counted
+    pub votes_root: String,
+    /// Whether the proposal currently passes
+    pub passed: bool,
+    /// Whether the proposal has been executed
+    pub executed: bool,
+}
+
+/// Output from the governance zkVM program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionOutput {
+    /// Hex state root before the batch
+    pub prior_state_root: String,
+    /// Hex state root after the batch
+    pub new_state_root: String,
+    /// Hex hash of the canonical encoding of the action batch
+    pub actions_hash: String,
+    /// Number of actions applied
+    pub actions_applied: u32,
+    /// Tallies of every proposal touched by the batch, ordered by ID
+    pub tallies: Vec<ProposalTally>,
+    /// Verifying key digest the reputation proofs were checked against
+    /// (verifiers must check it is the trust-game program's own key)
+    pub reputation_vkey: [u32; 8],
+}
+
+/// Hash of an action batch, so the output commits to exactly what was applied
+pub fn actions_hash(actions: &[GovernanceAction]) -> [u8; 32] {
+    let refs: Vec<&GovernanceAction> = actions.iter().collect();
+    let mut encoder = Encoder::new();
+    encoder.seq(&refs);
+    tagged_hash(ACTIONS_TAG, &encoder.finish())
+}
+
+/// Apply a batch of actions to a committed governance state
+/// Any invalid action fails the whole batch
+pub fn apply_transition(
+    input: TransitionInput,
+) -> Result<(GovernanceState, TransitionOutput), String> {
+    let prior_root = hash_from_hex(&input.prior_state_root)?;
+    if input.prior_state.state_root() != prior_root {
+        return Err("Prior state does not match prior state root".to_string());
+    }
+
+    let mut state = input.prior_state;
+    let mut touched: Vec<u32> = Vec::new();
+    let mut authorized: BTreeSet<[u8; 32]> = BTreeSet::new();
+
+    for (index, action) in input.actions.iter().enumerate() {
+        // Every signature in a batch covers the same root, so each one is used once
+        let replayed = action.signer().is_some()
+            && !authorized.insert(action.signing_message(&prior_root));
+        let proposal_id = if replayed {
+            Err("Action was already authorized in this batch".to_string())
+        } else {
+            action
+                .reputation_proof()
+                .map_or(Ok(()), |reputation| {
+                    reputation.check_recursion_vkey(&input.reputation_vkey)
+                })
+        }
+        .and_then(|_| action.apply(&mut state, &prior_root))
+        .map_err(|e| format!("Action {} failed: {}", index, e))?;
+        if let Some(id) = proposal_id {
+            if !touched.contains(&id) {
+                touched.push(id);
+            }
+        }
+    }
+    touched.sort();
+
+    let tallies = touched
+        .iter()
+        .filter_map(|id| state.get_proposal(*id))
+        .map(|p| ProposalTally {
+            proposal_id: p.id,
+            yes_voting_power: p.yes_voting_power,
+            no_voting_power: p.no_voting_power,
+            abstain_voting_power: p.abstain_voting_power,
+            option_voting_power: p.option_voting_power.clone(),
+            winning_option: p.winning_option,
+            votes_root: to_hex(&p.votes_root),
+            passed: p.has_passed(),
+            executed: p.executed,
+        })
+        .collect();
+
+    let output = TransitionOutput {
+        prior_state_root: to_hex(&prior_root),
+        new_state_root: to_hex(&state.state_root()),
+        actions_hash: to_hex(&actions_hash(&input.actions)),
+        actions_applied: input.actions.len() as u32,
+        tallies,
+        reputation_vkey: input.reputation_vkey,
+    };
+
+    Ok((state, output))
+}
+
+impl CanonicalEncode for GovernanceAction {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.encode_content(encoder);
+        if let Some((_, authorization)) = self.signer() {
+            authorization.encode(encoder);
+        }
+    }
+}
+
+impl GovernanceAction {
+    /// Canonical encoding of everything but the authorization
+    fn encode_content(&self, encoder: &mut Encoder) {
+        match self {
+            GovernanceAction::CreateProposal {
+                proposer,
+                proposer_reputation,
+                proposal_type,
+                description,
+                deposit,
+                ..
+            } => {
+                encoder
+                    .u8(0)
+                    .str(proposer)
+                    .bytes(&proposer_reputation.to_public_values());
+                proposal_type.encode(encoder);
+                encoder.str(description).u64(*deposit);
+            }
+            GovernanceAction::CreateMultiOptionProposal {
+                proposer,
+                proposer_reputation,
+                proposal_type,
+                description,
+                options,
+                tally_method,
+                deposit,
+                ..
+            } => {
+                encoder
+                    .u8(1)
+                    .str(proposer)
+                    .bytes(&proposer_reputation.to_public_values());
+                proposal_type.encode(encoder);
+                encoder.str(description).u32(options.len() as u32);
+                for option in options {
+                    encoder.str(option);
+                }
+                tally_method.encode(encoder);
+                encoder.u64(*deposit);
+            }
+            GovernanceAction::Vote {
+                proposal_id,
+                address,
+                vote,
+                voter_reputation,
+                timestamp,
+                ..
+            } => {
+                encoder.u8(2).u32(*proposal_id).str(address);
+                vote.encode(encoder);
+                encoder
+                    .bytes(&voter_reputation.to_public_values())
+                    .u64(*timestamp);
+            }
+            GovernanceAction::VoteOptions {
+                proposal_id,
+                address,
+                choices,
+                voter_reputation,
+                timestamp,
+                ..
+            } => {
+                encoder
+                    .u8(3)
+                    .u32(*proposal_id)
+                    .str(address)
+                    .u32(choices.len() as u32);
+                for choice in choices {
+                    encoder.u32(*choice);
+                }
+                encoder
+                    .bytes(&voter_reputation.to_public_values())
+                    .u64(*timestamp);
+            }
+            GovernanceAction::AdvanceRound { proposal_id } => {
+                encoder.u8(4).u32(*proposal_id);
+            }
+            GovernanceAction::ExecuteProposal { proposal_id } => {
+                encoder.u8(5).u32(*proposal_id);
+            }
+            GovernanceAction::SettleDeposit { proposal_id } => {
+                encoder.u8(6).u32(*proposal_id);
+            }
+            GovernanceAction::RegisterDependentApp {
+                app_id,
+                app_name,
+                min_reputation_tier,
+            } => {
+                encoder
+                    .u8(7)
+                    .str(app_id)
+                    .str(app_name)
+                    .u8(*min_reputation_tier);
+            }
+            GovernanceAction::CreatePayoffProposal {
+                proposer,
+                proposer_reputation,
+                description,
+                payoff_matrix,
+                deposit,
+                ..
+            } => {
+                encoder
+                    .u8(8)
+                    .str(proposer)
+                    .bytes(&proposer_reputation.to_public_values())
+                    .str(description);
+                payoff_matrix.encode(encoder);
+                encoder.u64(*deposit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::tests::{address, signing_key};
+    use crate::GamePolicy;
+
+    const REPUTATION_VKEY: [u32; 8] = [7; 8];
+
+    // Players, by signing key seed
+    const ALICE: u8 = 1;
+    const BOB: u8 = 2;
+    const CAROL: u8 = 3;
+    const DAVE: u8 = 4;
+    const MALLORY: u8 = 5;
+
+    /// Reputation output as committed by a trust-game proof
+    fn reputation(player: u8, tier: u8, reputation_score: u32) -> ProveOutput {
+        ProveOutput {
+            player_address: address(player),
+            total_moves: 20,
+            cooperative_moves: 20 * reputation_score / 100,
+            reputation_score,
+            tier,
+            voting_power: reputation_score,
+            sessions: 1,
+            recursion_vkey: None,
+            opponents: Vec::new(),
+            countersigned: true,
+            sybil_flags: Vec::new(),
+            game_policy: GamePolicy::default(),
+            game_ids: Vec::new(),
+            spell_commitment: String::new(),
+        }
+    }
+
+    /// Placeholder authorization, replaced by `sign`
+    fn unsigned() -> Authorization {
+        Authorization {
+            public_key: [0; 32],
+            signature: SchnorrSignature([0; 64]),
+        }
+    }
+
+    /// Authorize `action` with the key of `signer`, for a batch built on `prior`
+    fn sign(mut action: GovernanceAction, signer: u8, prior: &GovernanceState) -> GovernanceAction {
+        let key = signing_key(signer);
+        let message = action.signing_message(&prior.state_root());
+        let signature = key.sign_prehash_with_aux_rand(&message, &[0u8; 32]).unwrap();
+        let signed = Authorization {
+            public_key: key.verifying_key().to_bytes().into(),
+            signature: SchnorrSignature(signature.to_bytes()),
+        };
+        match &mut action {
+            GovernanceAction::CreateProposal { authorization, .. }
+            | GovernanceAction::CreateMultiOptionProposal { authorization, .. }
+            | GovernanceAction::CreatePayoffProposal { authorization, .. }
+            | GovernanceAction::Vote { authorization, .. }
+            | GovernanceAction::VoteOptions { authorization, .. } => *authorization = signed,
+            _ => {}
+        }
+        action
+    }
+
+    fn transition_input(
+        prior: &GovernanceState,
+        actions: Vec<GovernanceAction>,
+    ) -> TransitionInput {
+        TransitionInput {
+            prior_state_root: to_hex(&prior.state_root()),
+            prior_state: prior.clone(),
+            actions,
+            reputation_vkey: REPUTATION_VKEY,
+        }
+    }
+
+    fn create_action(prior: &GovernanceState) -> GovernanceAction {
+        let action = GovernanceAction::CreateProposal {
+            proposer: address(CAROL),
+            proposer_reputation: reputation(CAROL, 2, 90),
+            proposal_type: ProposalType::ChangePayoff,
+            description: "Change R to 3".to_string(),
+            deposit: 0,
+            authorization: unsigned(),
+        };
+        sign(action, CAROL, prior)
+    }
+
+    fn unsigned_vote(voter: u8, vote: Vote, voting_power: u32) -> GovernanceAction {
+        GovernanceAction::Vote {
+            proposal_id: 1,
+            address: address(voter),
+            vote,
+            voter_reputation: ProveOutput {
+                voting_power,
+                ..reputation(voter, 1, voting_power.min(100))
+            },
+            timestamp: 1000,
+            authorization: unsigned(),
+        }
+    }
+
+    fn vote_action(
+        prior: &GovernanceState,
+        voter: u8,
+        vote: Vote,
+        voting_power: u32,
+    ) -> GovernanceAction {
+        sign(unsigned_vote(voter, vote, voting_power), voter, prior)
+    }
+
+    #[test]
+    fn test_transition_matches_direct_application() {
+        let prior = GovernanceState::new();
+        let actions = vec![
+            create_action(&prior),
+            vote_action(&prior, ALICE, Vote::Yes, 112),
+            vote_action(&prior, BOB, Vote::No, 20),
+            GovernanceAction::ExecuteProposal { proposal_id: 1 },
+        ];
+
+        let (state, output) = apply_transition(transition_input(&prior, actions)).unwrap();
+
+        assert_eq!(output.prior_state_root, to_hex(&prior.state_root()));
+        assert_eq!(output.new_state_root, to_hex(&state.state_root()));
+        assert_eq!(output.actions_applied, 4);
+        assert_eq!(output.tallies.len(), 1);
+
+        let tally = &output.tallies[0];
+        assert_eq!(tally.proposal_id, 1);
+        assert_eq!(tally.yes_voting_power, 112);
+        assert_eq!(tally.no_voting_power, 20);
+        assert!(tally.passed);
+        assert!(tally.executed);
+    }
+
+    #[test]
+    fn test_transition_rejects_mismatched_root() {
+        let prior = GovernanceState::new();
+        let mut other = GovernanceState::new();
+        other
+            .register_dependent_app("nft_app".to_string(), "NFT".to_string(), 1)
+            .unwrap();
+
+        let result = apply_transition(TransitionInput {
+            prior_state_root: to_hex(&other.state_root()),
+            prior_state: prior.clone(),
+            actions: vec![create_action(&prior)],
+            reputation_vkey: REPUTATION_VKEY,
+        });
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_action_fails_batch() {
+        let prior = GovernanceState::new();
+
+        // Double vote in the same batch
+        let result = apply_transition(transition_input(
+            &prior,
+            vec![
+                create_action(&prior),
+                vote_action(&prior, ALICE, Vote::Yes, 112),
+                vote_action(&prior, ALICE, Vote::No, 112),
+            ],
+        ));
+
+        assert_eq!(
+            result.unwrap_err(),
+            format!("Action 2 failed: Player {} has already voted", address(ALICE))
+        );
+    }
+
+    #[test]
+    fn test_actions_require_player_signature() {
+        let prior = GovernanceState::new();
+        let batch = |vote: GovernanceAction| {
+            apply_transition(transition_input(&prior, vec![create_action(&prior), vote]))
+        };
+
+        // Mallory casts Alice's public reputation with her own key
+        let result = batch(sign(unsigned_vote(ALICE, Vote::No, 112), MALLORY, &prior));
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "Action 1 failed: {} is not the taproot address of the signing key",
+                address(ALICE)
+            )
+        );
+
+        // ...or claims Alice's key without her signature
+        let mut forged = vote_action(&prior, ALICE, Vote::Yes, 112);
+        if let GovernanceAction::Vote { vote, .. } = &mut forged {
+            *vote = Vote::No;
+        }
+        assert_eq!(
+            batch(forged).unwrap_err(),
+            format!("Action 1 failed: Invalid signature from {}", address(ALICE))
+        );
+
+        // Signed actions are only valid once, on the state they were signed for
+        let create = create_action(&prior);
+        let result = apply_transition(transition_input(&prior, vec![create.clone(), create]));
+        assert_eq!(
+            result.unwrap_err(),
+            "Action 1 failed: Action was already authorized in this batch"
+        );
+
+        let vote = vote_action(&prior, ALICE, Vote::Yes, 112);
+        let (state, _) = batch(vote.clone()).unwrap();
+        let result = apply_transition(transition_input(
+            &state,
+            vec![create_action(&state), vote],
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            format!("Action 1 failed: Invalid signature from {}", address(ALICE))
+        );
+    }
+
+    #[test]
+    fn test_proposer_tier_comes_from_reputation_proof() {
+        let prior = GovernanceState::new();
+        let propose = |proposer_reputation: ProveOutput| {
+            let action = GovernanceAction::CreateProposal {
+                proposer: address(CAROL),
+                proposer_reputation,
+                proposal_type: ProposalType::ChangePayoff,
+                description: "Change R to 3".to_string(),
+                deposit: 0,
+                authorization: unsigned(),
+            };
+            sign(action, CAROL, &prior)
+        };
+
+        // Proven Suspicious: below the default minimum tier
+        let result = apply_transition(transition_input(
+            &prior,
+            vec![propose(reputation(CAROL, 0, 20))],
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            "Action 0 failed: Proposer tier 0 is below minimum tier 1"
+        );
+
+        // Someone else's proof
+        let result = apply_transition(transition_input(
+            &prior,
+            vec![propose(reputation(DAVE, 2, 90))],
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "Action 0 failed: Reputation proof is for {}, not {}",
+                address(DAVE),
+                address(CAROL)
+            )
+        );
+
+        // A proof that folds in a session proven by another program
+        let mut chained = reputation(CAROL, 2, 90);
+        chained.recursion_vkey = Some([9; 8]);
+        let result = apply_transition(transition_input(&prior, vec![propose(chained)]));
+        assert_eq!(
+            result.unwrap_err(),
+            "Action 0 failed: Proof chains a session verified against a different program"
+        );
+
+        let (_, output) = apply_transition(transition_input(
+            &prior,
+            vec![propose(reputation(CAROL, 2, 90))],
+        ))
+        .unwrap();
+        assert_eq!(output.reputation_vkey, REPUTATION_VKEY);
+    }
+
+    #[test]
+    fn test_voting_power_comes_from_reputation_proof() {
+        let prior = GovernanceState::new();
+        let ballot = |voter: u8, voter_reputation: ProveOutput| {
+            let action = GovernanceAction::VoteOptions {
+                proposal_id: 1,
+                address: address(voter),
+                choices: vec![0],
+                voter_reputation,
+                timestamp: 1000,
+                authorization: unsigned(),
+            };
+            sign(action, voter, &prior)
+        };
+        let create = sign(
+            GovernanceAction::CreateMultiOptionProposal {
+                proposer: address(CAROL),
+                proposer_reputation: reputation(CAROL, 2, 90),
+                proposal_type: ProposalType::AddStrategy,
+                description: "Which strategy to add".to_string(),
+                options: vec!["Pavlov".to_string(), "Detective".to_string()],
+                tally_method: TallyMethod::Approval,
+                deposit: 0,
+                authorization: unsigned(),
+            },
+            CAROL,
+            &prior,
+        );
+
+        let (state, output) = apply_transition(transition_input(
+            &prior,
+            vec![create.clone(), ballot(ALICE, reputation(ALICE, 2, 80))],
+        ))
+        .unwrap();
+        assert_eq!(output.tallies[0].option_voting_power, vec![80, 0]);
+        let ballots = &state.voting_rounds[0].ballots;
+        assert_eq!(ballots[0].voter_reputation, 80);
+        assert_eq!(ballots[0].voting_power, 80);
+
+        // A reputation built on unsigned moves gives no voting power
+        let unsigned = ProveOutput {
+            countersigned: false,
+            ..reputation(BOB, 2, 80)
+        };
+        let (state, output) = apply_transition(transition_input(
+            &prior,
+            vec![create.clone(), ballot(BOB, unsigned)],
+        ))
+        .unwrap();
+        assert_eq!(output.tallies[0].option_voting_power, vec![0, 0]);
+        assert_eq!(state.voting_rounds[0].ballots[0].voting_power, 0);
+
+        // Voting with someone else's reputation is rejected
+        let result = apply_transition(transition_input(
+            &prior,
+            vec![create, ballot(MALLORY, reputation(ALICE, 2, 80))],
+        ));
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "Action 1 failed: Reputation proof is for {}, not {}",
+                address(ALICE),
+                address(MALLORY)
+            )
+        );
+    }
+
+    #[test]
+    fn test_actions_hash_depends_on_order() {
+        let prior = GovernanceState::new();
+        let a = vote_action(&prior, ALICE, Vote::Yes, 112);
+        let b = vote_action(&prior, BOB, Vote::No, 20);
+
+        assert_eq!(
+            actions_hash(&[a.clone(), b.clone()]),
+            actions_hash(&[a.clone(), b.clone()])
+        );
+        assert_ne!(actions_hash(&[a.clone(), b.clone()]), actions_hash(&[b, a]));
+    }
+
+    #[test]
+    fn test_payoff_proposal_checked_against_game_policy() {
+        let prior = GovernanceState::new();
+        let payoff_action = |r: i32| {
+            let action = GovernanceAction::CreatePayoffProposal {
+                proposer: address(CAROL),
+                proposer_reputation: reputation(CAROL, 2, 90),
+                description: format!("Change R to {}", r),
+                payoff_matrix: PayoffMatrix { r, s: -1, t: 3, p: 0 },
+                deposit: 0,
+                authorization: unsigned(),
+            };
+            sign(action, CAROL, &prior)
+        };
+
+        // R = 2 keeps the Prisoner's Dilemma
+        let (state, _) =
+            apply_transition(transition_input(&prior, vec![payoff_action(2)])).unwrap();
+        assert!(state.get_proposal(1).unwrap().payoff_matrix.is_some());
+
+        // R = 4 turns it into a Stag Hunt, which the default policy rejects
+        let result = apply_transition(transition_input(&prior, vec![payoff_action(4)]));
+        assert_eq!(
+            result.unwrap_err(),
+            "Action 0 failed: Stag Hunt games are not allowed by this deployment"
+        );
+    }
+}
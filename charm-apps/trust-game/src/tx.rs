@@ -0,0 +1,709 @@
+/**
+ * BITCOIN TRANSACTION BUILDER: COMMIT + SPELL PATTERN
+ *
+ * Builds the two transactions described in spell.yaml:
+ * 1. Commit tx: spends a segwit funding UTXO, outputs OP_RETURN <spell commitment>
+ *    plus a P2WSH change output locked to the spell script
+ *    (see spell.rs for what is committed)
+ * 2. Spell tx: spends the commit tx's change output with witness
+ *    [<signature>, <proof>, <spell_data>, <spell script>], outputs a bare
+ *    OP_RETURN plus change back to the funding script
+ *
+ * The spell script only accepts the exact spell data and a signature from the
+ * funding key:
+ *
+ *   OP_SHA256 <SHA-256(spell_data)> OP_EQUALVERIFY OP_DROP <public key> OP_CHECKSIG
+ *
+ * Only countersigned reputations are anchored: the spell carries voting power,
+ * which governance grants only to play signed by both players.
+ *
+ * Serialization follows BIP144 (segwit marker/flag, witness after outputs).
+ * Inputs are left unsigned for the wallet to sign: the funding input has no
+ * witness, and the spell input's signature slot is empty.
+ */
+
+use crate::encoding::{sha256, to_hex};
+use crate::prove::ProveOutput;
+use crate::spell::{commitment_script, encode_spell, spell_commitment};
+
+/// Script opcodes used by the builder
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_RETURN: u8 = 0x6a;
+const OP_DROP: u8 = 0x75;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_SHA256: u8 = 0xa8;
+const OP_CHECKSIG: u8 = 0xac;
+
+/// Bitcoin Core's default dust relay fee (sat/kvB)
+const DUST_RELAY_FEE: u64 = 3_000;
+
+/// Vbytes needed to spend a segwit output later (outpoint, sequence, witness share)
+const WITNESS_SPEND_VBYTES: u64 = 67;
+
+/// Sequence number signalling replace-by-fee
+const SEQUENCE_RBF: u32 = 0xffff_fffe;
+
+/// Reference to a previous transaction output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutPoint {
+    /// Previous txid in internal byte order (reverse of the displayed hex)
+    pub txid: [u8; 32],
+    /// Output index
+    pub vout: u32,
+}
+
+/// Transaction input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub previous_output: OutPoint,
+    pub script_sig: Vec<u8>,
+    pub sequence: u32,
+    /// Segwit witness stack (empty for non-witness inputs)
+    pub witness: Vec<Vec<u8>>,
+}
+
+/// Transaction output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    /// Value in satoshis
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+/// Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub version: i32,
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    pub lock_time: u32,
+}
+
+/// Append a Bitcoin CompactSize integer
+fn write_compact_size(buf: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0xfc => buf.push(n as u8),
+        0xfd..=0xffff => {
+            buf.push(0xfd);
+            buf.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            buf.push(0xfe);
+            buf.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            buf.push(0xff);
+            buf.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+/// Append a CompactSize-prefixed byte string
+fn write_var_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Double SHA-256, as used for txids
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    sha256(&sha256(data))
+}
+
+/// Display a txid (internal byte order) as conventional reversed hex
+pub fn txid_to_hex(txid: &[u8; 32]) -> String {
+    let mut reversed = *txid;
+    reversed.reverse();
+    to_hex(&reversed)
+}
+
+/// Parse a displayed (reversed) txid hex string into internal byte order
+pub fn txid_from_hex(hex: &str) -> Result<[u8; 32], String> {
+    let mut txid = crate::encoding::hash_from_hex(hex)?;
+    txid.reverse();
+    Ok(txid)
+}
+
+impl Transaction {
+    /// Whether any input carries witness data
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|input| !input.witness.is_empty())
+    }
+
+    fn serialize_inner(&self, include_witness: bool) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let with_witness = include_witness && self.has_witness();
+
+        buf.extend_from_slice(&self.version.to_le_bytes());
+        if with_witness {
+            // Segwit marker and flag
+            buf.extend_from_slice(&[0x00, 0x01]);
+        }
+
+        write_compact_size(&mut buf, self.inputs.len() as u64);
+        for input in &self.inputs {
+            buf.extend_from_slice(&input.previous_output.txid);
+            buf.extend_from_slice(&input.previous_output.vout.to_le_bytes());
+            write_var_bytes(&mut buf, &input.script_sig);
+            buf.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+
+        write_compact_size(&mut buf, self.outputs.len() as u64);
+        for output in &self.outputs {
+            buf.extend_from_slice(&output.value.to_le_bytes());
+            write_var_bytes(&mut buf, &output.script_pubkey);
+        }
+
+        if with_witness {
+            for input in &self.inputs {
+                write_compact_size(&mut buf, input.witness.len() as u64);
+                for item in &input.witness {
+                    write_var_bytes(&mut buf, item);
+                }
+            }
+        }
+
+        buf.extend_from_slice(&self.lock_time.to_le_bytes());
+        buf
+    }
+
+    /// Full serialization (BIP144 segwit format when witness data is present)
+    pub fn serialize(&self) -> Vec<u8> {
+        self.serialize_inner(true)
+    }
+
+    /// Legacy serialization without witness data (used for the txid)
+    pub fn serialize_without_witness(&self) -> Vec<u8> {
+        self.serialize_inner(false)
+    }
+
+    /// Transaction ID in internal byte order
+    pub fn txid(&self) -> [u8; 32] {
+        sha256d(&self.serialize_without_witness())
+    }
+
+    /// Witness transaction ID in internal byte order
+    pub fn wtxid(&self) -> [u8; 32] {
+        sha256d(&self.serialize())
+    }
+
+    /// Virtual size in vbytes (BIP141 weight / 4, rounded up)
+    pub fn vsize(&self) -> u64 {
+        let base = self.serialize_without_witness().len() as u64;
+        let total = self.serialize().len() as u64;
+        (base * 3 + total).div_ceil(4)
+    }
+}
+
+/// UTXO that funds the commit transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingUtxo {
+    pub outpoint: OutPoint,
+    /// Value in satoshis
+    pub value: u64,
+    /// Script of the funding output (must be segwit); change is returned here
+    pub script_pubkey: Vec<u8>,
+    /// Compressed public key that signs the spell transaction
+    pub public_key: [u8; 33],
+}
+
+/// Whether a script is a segwit output (BIP141 witness program)
+pub fn is_witness_program(script: &[u8]) -> bool {
+    let (version, program) = match script {
+        [version, push, program @ ..] if *push as usize == program.len() => (*version, program),
+        _ => return false,
+    };
+
+    match version {
+        OP_0 => program.len() == 20 || program.len() == 32,
+        OP_1..=OP_16 => (2..=40).contains(&program.len()),
+        _ => false,
+    }
+}
+
+/// Smallest value an output with this script may carry and still relay
+/// (Bitcoin Core's dust rule for segwit outputs)
+pub fn dust_limit(script_pubkey: &[u8]) -> u64 {
+    let mut output = Vec::new();
+    write_var_bytes(&mut output, script_pubkey);
+    let output_size = 8 + output.len() as u64;
+    (output_size + WITNESS_SPEND_VBYTES) * DUST_RELAY_FEE / 1_000
+}
+
+/// Witness script locking the commit change to the spell data and the funding key
+pub fn spell_script(spell_data: &[u8], public_key: &[u8; 33]) -> Vec<u8> {
+    let mut script = vec![OP_SHA256];
+    write_var_bytes(&mut script, &sha256(spell_data));
+    script.extend_from_slice(&[OP_EQUALVERIFY, OP_DROP]);
+    write_var_bytes(&mut script, public_key);
+    script.push(OP_CHECKSIG);
+    script
+}
+
+/// P2WSH output script for a witness script
+pub fn p2wsh_script(witness_script: &[u8]) -> Vec<u8> {
+    let mut script = vec![OP_0];
+    write_var_bytes(&mut script, &sha256(witness_script));
+    script
+}
+
+/// Reject change below the dust limit of its script
+fn check_dust(label: &str, value: u64, script_pubkey: &[u8]) -> Result<(), String> {
+    let limit = dust_limit(script_pubkey);
+    if value < limit {
+        return Err(format!(
+            "{} of {} sats is below the dust limit of {} sats",
+            label, value, limit
+        ));
+    }
+    Ok(())
+}
+
+/// Fees (in satoshis) paid by each transaction of the pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpellFees {
+    pub commit_fee: u64,
+    pub spell_fee: u64,
+}
+
+/// The commit and spell transactions, ready for signing and broadcast
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellTransactions {
+    pub commit_tx: Transaction,
+    pub spell_tx: Transaction,
+}
+
+/// Build the commit + spell transaction pair for a proven reputation
+/// `program_vkey` is the trust-game program's vkey digest; proofs chaining sessions
+/// verified against any other key are rejected, as are reputations with unsigned moves
+pub fn build_spell_transactions(
+    output: &ProveOutput,
+    proof: &[u8],
+    program_vkey: &[u32; 8],
+    funding: &FundingUtxo,
+    fees: SpellFees,
+) -> Result<SpellTransactions, String> {
+    output.check_recursion_vkey(program_vkey)?;
+    if !output.countersigned {
+        return Err("Reputation is not countersigned and carries no voting power".to_string());
+    }
+
+    if !is_witness_program(&funding.script_pubkey) {
+        return Err("Funding script is not a segwit output".to_string());
+    }
+
+    let spell_data = encode_spell(output);
+    let witness_script = spell_script(&spell_data, &funding.public_key);
+    let commit_script = p2wsh_script(&witness_script);
+
+    let commit_change = funding
+        .value
+        .checked_sub(fees.commit_fee)
+        .ok_or("Funding UTXO does not cover commit fee".to_string())?;
+    let spell_change = commit_change
+        .checked_sub(fees.spell_fee)
+        .ok_or("Funding UTXO does not cover spell fee".to_string())?;
+    check_dust("Commit change", commit_change, &commit_script)?;
+    check_dust("Spell change", spell_change, &funding.script_pubkey)?;
+
+    // Phase 1: commit to the spell hash
+    let commit_tx = Transaction {
+        version: 2,
+        inputs: vec![TxIn {
+            previous_output: funding.outpoint.clone(),
+            script_sig: Vec::new(), // Signed by the wallet
+            sequence: SEQUENCE_RBF,
+            witness: Vec::new(),
+        }],
+        outputs: vec![
+            TxOut {
+                value: 0,
+                script_pubkey: commitment_script(&spell_commitment(output)),
+            },
+            TxOut {
+                value: commit_change,
+                script_pubkey: commit_script,
+            },
+        ],
+        lock_time: 0,
+    };
+
+    // Phase 2: spend the commit change with proof and spell in the witness
+    let spell_tx = Transaction {
+        version: 2,
+        inputs: vec![TxIn {
+            previous_output: OutPoint {
+                txid: commit_tx.txid(),
+                vout: 1,
+            },
+            script_sig: Vec::new(),
+            sequence: SEQUENCE_RBF,
+            // Signature slot is filled by the wallet
+            witness: vec![Vec::new(), proof.to_vec(), spell_data, witness_script],
+        }],
+        outputs: vec![
+            TxOut {
+                value: 0,
+                script_pubkey: vec![OP_RETURN],
+            },
+            TxOut {
+                value: spell_change,
+                script_pubkey: funding.script_pubkey.clone(),
+            },
+        ],
+        lock_time: 0,
+    };
+
+    Ok(SpellTransactions { commit_tx, spell_tx })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prove::OpponentRecord;
+    use crate::GamePolicy;
+    use crate::encoding::from_hex;
+
+    const PROGRAM_VKEY: [u32; 8] = [7; 8];
+
+    fn sample_output() -> ProveOutput {
+        ProveOutput {
+            player_address: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+            total_moves: 5,
+            cooperative_moves: 3,
+            reputation_score: 60,
+            tier: 1,
+            voting_power: 60,
+            sessions: 1,
+            recursion_vkey: None,
+            opponents: vec![OpponentRecord {
+                opponent: String::new(),
+                total_moves: 5,
+                cooperative_moves: 3,
+                matches: 1,
+                payoff: 6,
+                opponent_payoff: 6,
+                countersigned_matches: 1,
+            }],
+            countersigned: true,
+            sybil_flags: Vec::new(),
+            game_policy: GamePolicy::default(),
+            game_ids: Vec::new(),
+            spell_commitment: String::new(),
+        }
+    }
+
+    fn sample_funding() -> FundingUtxo {
+        FundingUtxo {
+            outpoint: OutPoint {
+                txid: txid_from_hex(
+                    "1111111111111111111111111111111111111111111111111111111111111122",
+                )
+                .unwrap(),
+                vout: 1,
+            },
+            value: 100_000,
+            // P2WPKH for tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx
+            script_pubkey: from_hex("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap(),
+            // Key hashing to the P2WPKH program above (BIP173 test vector)
+            public_key: from_hex(
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_compact_size() {
+        let mut buf = Vec::new();
+        write_compact_size(&mut buf, 0xfc);
+        write_compact_size(&mut buf, 0xfd);
+        write_compact_size(&mut buf, 0x1_0000);
+        assert_eq!(to_hex(&buf), "fcfdfd00fe00000100");
+    }
+
+    #[test]
+    fn test_txid_hex_roundtrip() {
+        let hex = "1111111111111111111111111111111111111111111111111111111111111122";
+        let txid = txid_from_hex(hex).unwrap();
+        assert_eq!(txid[0], 0x22);
+        assert_eq!(txid_to_hex(&txid), hex);
+    }
+
+    #[test]
+    fn test_commit_tx_golden_vector() {
+        let txs = build_spell_transactions(
+            &sample_output(),
+            &[0xde, 0xad, 0xbe, 0xef],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 500,
+                spell_fee: 1_000,
+            },
+        )
+        .unwrap();
+
+        let commit = &txs.commit_tx;
+        assert!(!commit.has_witness());
+        assert_eq!(
+            to_hex(&commit.serialize()),
+            "0200000001\
+             2211111111111111111111111111111111111111111111111111111111111111\
+             01000000\
+             00\
+             feffffff\
+             02\
+             0000000000000000\
+             22\
+             6a20\
+             02bc1ed5fedcf450b562f82a2f169617dcc0ee04f3c8593e259733b01c82ffd3\
+             ac84010000000000\
+             22\
+             002002b3c6776b318b6133ee6c2a82cafcbe6a4b4668b456e7075534ec0e83ba5863\
+             00000000"
+        );
+        assert_eq!(
+            txid_to_hex(&commit.txid()),
+            "504152ec82c20e2a0925e47a3631bc3f2ed7569865cce37cea7f53b535a0ba58"
+        );
+        assert_eq!(commit.txid(), commit.wtxid());
+    }
+
+    #[test]
+    fn test_spell_tx_golden_vector() {
+        let txs = build_spell_transactions(
+            &sample_output(),
+            &[0xde, 0xad, 0xbe, 0xef],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 500,
+                spell_fee: 1_000,
+            },
+        )
+        .unwrap();
+
+        let spell = &txs.spell_tx;
+        assert!(spell.has_witness());
+        assert_eq!(spell.inputs[0].previous_output.txid, txs.commit_tx.txid());
+        assert_eq!(spell.inputs[0].previous_output.vout, 1);
+        assert_eq!(spell.inputs[0].witness[2], encode_spell(&sample_output()));
+
+        assert_eq!(
+            to_hex(&spell.serialize()),
+            "02000000\
+             0001\
+             01\
+             58baa035b5537fea7ce3cc659856d72e3fbc31367ae425092a0ec282ec524150\
+             01000000\
+             00\
+             feffffff\
+             02\
+             0000000000000000\
+             01\
+             6a\
+             c480010000000000\
+             16\
+             0014751e76e8199196d454941c45d1b3a323f1433bd6\
+             04\
+             00\
+             04\
+             deadbeef\
+             7b\
+             01\
+             0000002a\
+             7462317177353038643671656a7874646734793572337a6172766172793063357877376b78706a7a7378\
+             00000005000000030000003c01\
+             0000003c\
+             00000001\
+             00\
+             00000001\
+             00000000\
+             000000050000000300000001\
+             0000000000000006\
+             0000000000000006\
+             00000001\
+             01\
+             00000000\
+             00000001\
+             00\
+             00000000\
+             47\
+             a820\
+             c580ef03ba145a9cc1a0f150dda21f6e1998fb6dca48d1ba4884d898d46db182\
+             8875\
+             21\
+             0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             ac\
+             00000000"
+        );
+        assert_eq!(
+            txid_to_hex(&spell.txid()),
+            "bfcdadd42430f7bff6576207d4c3971b751d2deb23e00573aadf571f2dacb631"
+        );
+        assert_eq!(
+            txid_to_hex(&spell.wtxid()),
+            "70e4f5cb44f5cab5f88d1d4e23a9f725622e78fa1924b3eb9549b5c641114e9b"
+        );
+        assert_ne!(spell.txid(), spell.wtxid());
+    }
+
+    #[test]
+    fn test_golden_vectors_match_bitcoin_crate() {
+        use bitcoin::consensus::encode::{deserialize, serialize};
+
+        let txs = build_spell_transactions(
+            &sample_output(),
+            &[0xde, 0xad, 0xbe, 0xef],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 500,
+                spell_fee: 1_000,
+            },
+        )
+        .unwrap();
+
+        for tx in [&txs.commit_tx, &txs.spell_tx] {
+            let decoded: bitcoin::Transaction = deserialize(&tx.serialize()).unwrap();
+            assert_eq!(serialize(&decoded), tx.serialize());
+            assert_eq!(decoded.compute_txid().to_string(), txid_to_hex(&tx.txid()));
+            assert_eq!(decoded.compute_wtxid().to_string(), txid_to_hex(&tx.wtxid()));
+            assert_eq!(decoded.vsize() as u64, tx.vsize());
+        }
+
+        // The commit change is the P2WSH of the script revealed in the spell witness
+        let witness_script = bitcoin::ScriptBuf::from(txs.spell_tx.inputs[0].witness[3].clone());
+        assert_eq!(
+            bitcoin::ScriptBuf::new_p2wsh(&witness_script.wscript_hash()).into_bytes(),
+            txs.commit_tx.outputs[1].script_pubkey
+        );
+        assert_eq!(
+            witness_script.to_asm_string(),
+            format!(
+                "OP_SHA256 OP_PUSHBYTES_32 {} OP_EQUALVERIFY OP_DROP \
+                 OP_PUSHBYTES_33 {} OP_CHECKSIG",
+                to_hex(&sha256(&encode_spell(&sample_output()))),
+                to_hex(&sample_funding().public_key)
+            )
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_segwit_funding() {
+        let mut funding = sample_funding();
+        // P2PKH for the same key hash
+        funding.script_pubkey =
+            from_hex("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac").unwrap();
+
+        let result = build_spell_transactions(
+            &sample_output(),
+            &[],
+            &PROGRAM_VKEY,
+            &funding,
+            SpellFees {
+                commit_fee: 500,
+                spell_fee: 1_000,
+            },
+        );
+        assert_eq!(result.unwrap_err(), "Funding script is not a segwit output");
+
+        assert!(is_witness_program(&sample_funding().script_pubkey));
+        assert!(!is_witness_program(&[OP_0, 0x14]));
+        assert!(!is_witness_program(&[OP_RETURN]));
+    }
+
+    #[test]
+    fn test_rejects_dust_change() {
+        assert_eq!(dust_limit(&sample_funding().script_pubkey), 294);
+        assert_eq!(dust_limit(&p2wsh_script(&[])), 330);
+
+        // Commit change just below the P2WSH dust limit
+        let result = build_spell_transactions(
+            &sample_output(),
+            &[],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 100_000 - 329,
+                spell_fee: 0,
+            },
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            "Commit change of 329 sats is below the dust limit of 330 sats"
+        );
+
+        // Spell change just below the P2WPKH dust limit
+        let result = build_spell_transactions(
+            &sample_output(),
+            &[],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 500,
+                spell_fee: 99_500 - 293,
+            },
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            "Spell change of 293 sats is below the dust limit of 294 sats"
+        );
+    }
+
+    #[test]
+    fn test_rejects_foreign_recursion_vkey() {
+        let fees = SpellFees {
+            commit_fee: 500,
+            spell_fee: 1_000,
+        };
+        let mut output = sample_output();
+        output.recursion_vkey = Some(PROGRAM_VKEY);
+        assert!(
+            build_spell_transactions(&output, &[], &PROGRAM_VKEY, &sample_funding(), fees).is_ok()
+        );
+
+        output.recursion_vkey = Some([9; 8]);
+        let result = build_spell_transactions(&output, &[], &PROGRAM_VKEY, &sample_funding(), fees);
+        assert_eq!(
+            result.unwrap_err(),
+            "Proof chains a session verified against a different program"
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsigned_reputation() {
+        let output = ProveOutput {
+            countersigned: false,
+            ..sample_output()
+        };
+        let fees = SpellFees {
+            commit_fee: 500,
+            spell_fee: 1_000,
+        };
+        let result = build_spell_transactions(&output, &[], &PROGRAM_VKEY, &sample_funding(), fees);
+        assert_eq!(
+            result.unwrap_err(),
+            "Reputation is not countersigned and carries no voting power"
+        );
+    }
+
+    #[test]
+    fn test_insufficient_funds() {
+        let result = build_spell_transactions(
+            &sample_output(),
+            &[],
+            &PROGRAM_VKEY,
+            &sample_funding(),
+            SpellFees {
+                commit_fee: 60_000,
+                spell_fee: 60_000,
+            },
+        );
+        assert!(result.is_err());
+    }
+}